@@ -0,0 +1,276 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+pub struct AchievementPlugin;
+
+impl Plugin for AchievementPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AchievementProgress>()
+            .add_event::<AchievementUnlocked>()
+            .add_systems(Startup, load_achievement_progress)
+            .add_systems(Update, check_achievements.run_if(in_game_not_paused));
+    }
+}
+
+/// A long-term goal, reached during a single run. Unlocked achievements are kept between runs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum Achievement {
+    Survivor,
+    Exterminator,
+    OrbWeaver,
+    Veteran,
+    Evolved,
+    Aftershock,
+}
+
+/// What has to happen in a run to unlock an achievement
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AchievementGoal {
+    SurviveSeconds(u64),
+    KillEnemies(u32),
+    ReachOrbs(u32),
+    ReachLevel(u32),
+    EvolveWeapons(u32),
+    UseUltimate(u32),
+}
+
+/// Something that can't be used until an achievement is unlocked
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AchievementReward {
+    Character(CharacterKind),
+    Weapon(WeaponKind),
+}
+
+impl Achievement {
+    pub const ALL: [Self; 6] = [
+        Self::Survivor,
+        Self::Exterminator,
+        Self::OrbWeaver,
+        Self::Veteran,
+        Self::Evolved,
+        Self::Aftershock,
+    ];
+
+    pub fn goal(&self) -> AchievementGoal {
+        match self {
+            Achievement::Survivor => AchievementGoal::SurviveSeconds(10 * 60),
+            Achievement::Exterminator => AchievementGoal::KillEnemies(1000),
+            Achievement::OrbWeaver => AchievementGoal::ReachOrbs(5),
+            Achievement::Veteran => AchievementGoal::ReachLevel(20),
+            Achievement::Evolved => AchievementGoal::EvolveWeapons(1),
+            Achievement::Aftershock => AchievementGoal::UseUltimate(1),
+        }
+    }
+
+    pub fn reward(&self) -> Option<AchievementReward> {
+        match self {
+            Achievement::Survivor => Some(AchievementReward::Character(CharacterKind::Juggernaut)),
+            Achievement::Exterminator => {
+                Some(AchievementReward::Character(CharacterKind::Engineer))
+            }
+            Achievement::OrbWeaver => {
+                Some(AchievementReward::Weapon(WeaponKind::OrbitalProjectile))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Achievement::Survivor => write!(f, "Survivor"),
+            Achievement::Exterminator => write!(f, "Exterminator"),
+            Achievement::OrbWeaver => write!(f, "Orb Weaver"),
+            Achievement::Veteran => write!(f, "Veteran"),
+            Achievement::Evolved => write!(f, "Evolved"),
+            Achievement::Aftershock => write!(f, "Aftershock"),
+        }
+    }
+}
+
+impl fmt::Display for AchievementGoal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AchievementGoal::SurviveSeconds(secs) => write!(f, "Survive {} minutes", secs / 60),
+            AchievementGoal::KillEnemies(kills) => write!(f, "Kill {} enemies in one run", kills),
+            AchievementGoal::ReachOrbs(orbs) => write!(f, "Reach {} orbs", orbs),
+            AchievementGoal::ReachLevel(level) => write!(f, "Reach level {}", level),
+            AchievementGoal::EvolveWeapons(1) => write!(f, "Evolve a weapon"),
+            AchievementGoal::EvolveWeapons(count) => write!(f, "Evolve {} weapons", count),
+            AchievementGoal::UseUltimate(1) => write!(f, "Use your ultimate"),
+            AchievementGoal::UseUltimate(count) => write!(f, "Use your ultimate {} times", count),
+        }
+    }
+}
+
+impl fmt::Display for AchievementReward {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AchievementReward::Character(character) => write!(f, "Unlocks the {}", character),
+            AchievementReward::Weapon(weapon) => write!(f, "Unlocks the {} weapon", weapon),
+        }
+    }
+}
+
+/// How far the current run has got, for checking [`AchievementGoal`]s
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RunProgress {
+    pub seconds: u64,
+    pub kills: u32,
+    /// Highest number of orbs held by any player
+    pub orbs: u32,
+    pub level: u32,
+    /// Highest number of evolutions taken by any player
+    pub evolutions: u32,
+    pub ultimates: u32,
+}
+
+impl AchievementGoal {
+    pub fn is_met(&self, run: &RunProgress) -> bool {
+        match *self {
+            AchievementGoal::SurviveSeconds(secs) => run.seconds >= secs,
+            AchievementGoal::KillEnemies(kills) => run.kills >= kills,
+            AchievementGoal::ReachOrbs(orbs) => run.orbs >= orbs,
+            AchievementGoal::ReachLevel(level) => run.level >= level,
+            AchievementGoal::EvolveWeapons(count) => run.evolutions >= count,
+            AchievementGoal::UseUltimate(count) => run.ultimates >= count,
+        }
+    }
+}
+
+/// The achievements unlocked so far. Saved to [`GameStorage`] under
+/// [`AchievementProgress::STORAGE_KEY`].
+#[derive(Resource, Default, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource, Default, Debug)]
+#[serde(default)]
+pub struct AchievementProgress {
+    pub unlocked: Vec<Achievement>,
+}
+
+impl AchievementProgress {
+    pub const STORAGE_KEY: &'static str = "achievements";
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked.contains(&achievement)
+    }
+
+    /// Unlocks every achievement whose goal the run has met, returning the newly unlocked ones
+    pub fn unlock_met(&mut self, run: &RunProgress) -> Vec<Achievement> {
+        let newly_unlocked: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|achievement| !self.is_unlocked(*achievement) && achievement.goal().is_met(run))
+            .collect();
+        self.unlocked.extend(newly_unlocked.iter().copied());
+        newly_unlocked
+    }
+
+    /// Returns the achievement needed to use a reward, or `None` if it is already available
+    pub fn required_for(&self, reward: AchievementReward) -> Option<Achievement> {
+        Achievement::ALL.into_iter().find(|achievement| {
+            achievement.reward() == Some(reward) && !self.is_unlocked(*achievement)
+        })
+    }
+
+    pub fn is_character_unlocked(&self, character: CharacterKind) -> bool {
+        self.required_for(AchievementReward::Character(character))
+            .is_none()
+    }
+
+    /// Returns the weapons that can't be offered as power ups yet
+    pub fn locked_weapons(&self) -> Vec<WeaponKind> {
+        WeaponKind::ALL
+            .into_iter()
+            .filter(|weapon| {
+                self.required_for(AchievementReward::Weapon(*weapon))
+                    .is_some()
+            })
+            .collect()
+    }
+}
+
+/// Sent when an achievement is unlocked
+#[derive(Event, Debug)]
+pub struct AchievementUnlocked(pub Achievement);
+
+fn load_achievement_progress(mut commands: Commands, storage: Res<GameStorage>) {
+    if let Some(progress) =
+        storage.load_json::<AchievementProgress>(AchievementProgress::STORAGE_KEY)
+    {
+        commands.insert_resource(progress);
+    }
+}
+
+fn check_achievements(
+    score: Res<PlayerScore>,
+    timer: Res<GameTimer>,
+    meter: Res<UltimateMeter>,
+    stats_query: Query<&PlayerStats>,
+    storage: Res<GameStorage>,
+    mut progress: ResMut<AchievementProgress>,
+    mut unlocked_writer: EventWriter<AchievementUnlocked>,
+) {
+    let run = RunProgress {
+        seconds: timer.0.elapsed().as_secs(),
+        kills: score.enemies_killed,
+        orbs: stats_query
+            .iter()
+            .map(|stats| stats.get_weapon_level(WeaponKind::Orb))
+            .max()
+            .unwrap_or_default(),
+        level: score.level,
+        evolutions: stats_query
+            .iter()
+            .map(|stats| stats.evolutions.len() as u32)
+            .max()
+            .unwrap_or_default(),
+        ultimates: meter.uses,
+    };
+
+    // only mark the resource as changed when something unlocks
+    let unlocked = progress.bypass_change_detection().unlock_met(&run);
+    if unlocked.is_empty() {
+        return;
+    }
+    progress.set_changed();
+    storage.save_json(AchievementProgress::STORAGE_KEY, &*progress);
+    for achievement in unlocked {
+        unlocked_writer.send(AchievementUnlocked(achievement));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn achievements_unlock_once_and_unlock_rewards() {
+        let mut progress = AchievementProgress::default();
+        assert!(!progress.is_character_unlocked(CharacterKind::Juggernaut));
+        assert!(progress.is_character_unlocked(CharacterKind::Pilot));
+        assert_eq!(
+            progress.locked_weapons(),
+            vec![WeaponKind::OrbitalProjectile]
+        );
+
+        let mut run = RunProgress {
+            seconds: 9 * 60,
+            orbs: 5,
+            ..default()
+        };
+        assert_eq!(progress.unlock_met(&run), vec![Achievement::OrbWeaver]);
+        assert!(progress.locked_weapons().is_empty());
+
+        run.seconds = 10 * 60;
+        assert_eq!(progress.unlock_met(&run), vec![Achievement::Survivor]);
+        assert!(progress.is_character_unlocked(CharacterKind::Juggernaut));
+
+        // nothing unlocks twice
+        assert!(progress.unlock_met(&run).is_empty());
+    }
+}
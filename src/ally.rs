@@ -0,0 +1,322 @@
+use std::f32::consts::TAU;
+
+use bevy::{color::palettes::css, prelude::*};
+
+use crate::*;
+
+pub struct AllyPlugin;
+
+impl Plugin for AllyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AllyResources>().add_systems(
+            Update,
+            (
+                (setup_new_drones, setup_new_turrets),
+                (follow_player, pick_ally_targets, place_turrets).run_if(not_paused),
+                update_drones_on_stats_change,
+            )
+                .run_if(in_game),
+        );
+    }
+}
+
+/// A friendly entity that fires its own [`Weapon`] at a target it picks itself. It uses the stats of
+/// the player that owns it.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Ally {
+    pub target: Option<Entity>,
+}
+
+/// An [`Ally`] that follows the player.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Drone {
+    pub index: u32,
+}
+
+/// A stationary [`Ally`] placed on the surface of the planet.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Turret;
+
+#[derive(Bundle)]
+pub struct DroneBundle {
+    pub name: Name,
+    pub ally: Ally,
+    pub owned_by: OwnedBy,
+    pub drone: Drone,
+    pub state_scoped: StateScoped<AppState>,
+    pub spatial: SpatialBundle,
+}
+
+impl DroneBundle {
+    pub fn new(owner: Entity, pos: Vec3, index: u32) -> Self {
+        Self {
+            name: Name::new("Drone"),
+            ally: Ally::default(),
+            owned_by: OwnedBy(owner),
+            drone: Drone { index },
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(
+                pos.normalize() * get_drone_height(),
+            )),
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct TurretBundle {
+    pub name: Name,
+    pub ally: Ally,
+    pub owned_by: OwnedBy,
+    pub turret: Turret,
+    pub state_scoped: StateScoped<AppState>,
+    pub spatial: SpatialBundle,
+    pub lifetime: Lifetime,
+}
+
+impl TurretBundle {
+    pub fn new(owner: Entity, pos: Vec3) -> Self {
+        let normal = pos.normalize();
+        Self {
+            name: Name::new("Turret"),
+            ally: Ally::default(),
+            owned_by: OwnedBy(owner),
+            turret: Turret,
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::from_transform(
+                Transform::from_translation(
+                    normal * (constants::PLANET_RADIUS + constants::TURRET_SIZE / 2.),
+                )
+                .with_rotation(Quat::from_rotation_arc(Vec3::Y, normal)),
+            ),
+            lifetime: Lifetime::from_seconds(constants::TURRET_LIFETIME_SECS),
+        }
+    }
+}
+
+/// Timer for a player placing new turrets
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct TurretPlacer(pub Timer);
+
+impl Default for TurretPlacer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            constants::TURRET_PLACE_COOLDOWN_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct AllyResources {
+    pub drone_mesh: Option<Handle<Mesh>>,
+    pub drone_material: Option<Handle<StandardMaterial>>,
+    pub turret_mesh: Option<Handle<Mesh>>,
+    pub turret_material: Option<Handle<StandardMaterial>>,
+}
+
+impl AllyResources {
+    pub fn get_or_create_drone_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(ref material) = self.drone_material {
+            material.clone()
+        } else {
+            let material = materials.add(StandardMaterial {
+                base_color: css::AQUA.into(),
+                ..default()
+            });
+            self.drone_material = Some(material.clone());
+            material
+        }
+    }
+
+    pub fn get_or_create_drone_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        if let Some(ref mesh) = self.drone_mesh {
+            mesh.clone()
+        } else {
+            let mesh = meshes.add(Sphere::new(constants::DRONE_SIZE / 2.));
+            self.drone_mesh = Some(mesh.clone());
+            mesh
+        }
+    }
+
+    pub fn get_or_create_turret_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(ref material) = self.turret_material {
+            material.clone()
+        } else {
+            let material = materials.add(StandardMaterial {
+                base_color: css::TEAL.into(),
+                ..default()
+            });
+            self.turret_material = Some(material.clone());
+            material
+        }
+    }
+
+    pub fn get_or_create_turret_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        if let Some(ref mesh) = self.turret_mesh {
+            mesh.clone()
+        } else {
+            let mesh = meshes.add(Cylinder::new(
+                constants::TURRET_SIZE / 2.,
+                constants::TURRET_SIZE,
+            ));
+            self.turret_mesh = Some(mesh.clone());
+            mesh
+        }
+    }
+}
+
+fn setup_new_drones(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), Added<Drone>>,
+    mut ally_resources: ResMut<AllyResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, transform) in query.iter() {
+        commands.entity(entity).insert(MaterialMeshBundle {
+            mesh: ally_resources.get_or_create_drone_mesh(&mut meshes),
+            material: ally_resources.get_or_create_drone_material(&mut materials),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
+
+fn setup_new_turrets(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), Added<Turret>>,
+    mut ally_resources: ResMut<AllyResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, transform) in query.iter() {
+        commands.entity(entity).insert(MaterialMeshBundle {
+            mesh: ally_resources.get_or_create_turret_mesh(&mut meshes),
+            material: ally_resources.get_or_create_turret_material(&mut materials),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
+
+fn update_drones_on_stats_change(
+    mut commands: Commands,
+    drone_query: Query<(Entity, &OwnedBy), With<Drone>>,
+    player_query: Query<(Entity, &GlobalTransform, &PlayerStats), Changed<PlayerStats>>,
+) {
+    for (player_entity, player_transform, stats) in player_query.iter() {
+        let owned_drones = || {
+            drone_query
+                .iter()
+                .filter(move |(_, owned_by)| owned_by.0 == player_entity)
+                .map(|(e, _)| e)
+        };
+        if owned_drones().count() as u32 == stats.drones {
+            continue;
+        }
+
+        // de-spawn existing drones so the new ones are spaced evenly around the player
+        for e in owned_drones() {
+            commands.entity(e).despawn_recursive();
+        }
+
+        let player_pos = player_transform.translation();
+        for index in 0..stats.drones {
+            commands
+                .spawn(DroneBundle::new(player_entity, player_pos, index))
+                .with_children(|p| {
+                    p.spawn(WeaponBundle::new());
+                });
+        }
+    }
+}
+
+/// System that moves drones towards their spot around their player, lagging behind as it moves
+fn follow_player(
+    time: Res<Time>,
+    mut query: Query<(&Drone, &OwnedBy, &mut Transform)>,
+    player_query: Query<(&Player, &GlobalTransform, &PlayerStats), Without<Drone>>,
+) {
+    // fraction of the remaining distance to cover this frame
+    let t = 1. - (-constants::DRONE_FOLLOW_SPEED * time.delta_seconds()).exp();
+
+    for (drone, owned_by, mut transform) in query.iter_mut() {
+        let Ok((player, player_transform, stats)) = player_query.get(owned_by.0) else {
+            continue;
+        };
+        let player_pos = player_transform.translation();
+        let towards_camera = player_pos.normalize();
+
+        // spread the drones evenly around the player
+        let angle = TAU * drone.index as f32 / stats.drones.max(1) as f32;
+        let offset = Quat::from_axis_angle(towards_camera, angle)
+            * player.up.normalize()
+            * constants::DRONE_FOLLOW_DISTANCE;
+        let target_pos = player_pos + offset;
+
+        // rotate part of the way around the planet towards the target
+        let rot =
+            Quat::from_rotation_arc(transform.translation.normalize(), target_pos.normalize());
+        transform.rotate_around(Vec3::ZERO, Quat::IDENTITY.slerp(rot, t));
+        transform.translation = transform.translation.normalize() * get_drone_height();
+    }
+}
+
+/// System that points each ally at the closest enemy in range
+fn pick_ally_targets(
+    mut query: Query<(&mut Ally, &GlobalTransform)>,
+    enemy_query: Query<(Entity, &GlobalTransform), With<Enemy>>,
+) {
+    for (mut ally, transform) in query.iter_mut() {
+        let pos = transform.translation();
+        ally.target = enemy_query
+            .iter()
+            .map(|(e, t)| (e, t.translation().distance(pos)))
+            .filter(|(_, dist)| *dist <= constants::ALLY_TARGET_RANGE)
+            .reduce(|acc, e| if e.1 < acc.1 { e } else { acc })
+            .map(|(e, _)| e);
+    }
+}
+
+/// System that places a turret where each player stands, while they have fewer than their max placed
+fn place_turrets(
+    mut commands: Commands,
+    time: Res<Time>,
+    turret_query: Query<&OwnedBy, With<Turret>>,
+    mut player_query: Query<(Entity, &GlobalTransform, &PlayerStats, &mut TurretPlacer)>,
+) {
+    for (player_entity, player_transform, stats, mut placer) in player_query.iter_mut() {
+        placer.0.tick(time.delta());
+        let placed = turret_query
+            .iter()
+            .filter(|owned_by| owned_by.0 == player_entity)
+            .count() as u32;
+        if !placer.0.just_finished() || placed >= stats.turrets {
+            continue;
+        }
+
+        commands
+            .spawn(TurretBundle::new(
+                player_entity,
+                player_transform.translation(),
+            ))
+            .with_children(|p| {
+                p.spawn(WeaponBundle::new());
+            });
+    }
+}
+
+fn get_drone_height() -> f32 {
+    constants::PLANET_RADIUS + constants::DRONE_HEIGHT
+}
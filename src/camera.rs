@@ -3,17 +3,24 @@ use bevy::{
     pbr::VolumetricFogSettings,
     prelude::*,
 };
+use rand::Rng;
 
-use crate::{constants, game_state::*, Player};
+use crate::{constants, game_state::*, GameRng, Player, RngStream, Settings};
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera).add_systems(
-            Update,
-            camera_follow_player.run_if(in_state(AppState::Game)),
-        );
+        app.init_resource::<ScreenShake>()
+            .add_systems(Startup, spawn_camera)
+            .add_systems(
+                Update,
+                (
+                    camera_follow_player.run_if(in_state(AppState::Game)),
+                    apply_camera_settings.run_if(resource_changed::<Settings>),
+                ),
+            )
+            .add_systems(OnEnter(AppState::Game), reset_screen_shake);
     }
 }
 
@@ -21,6 +28,21 @@ impl Plugin for CameraPlugin {
 #[reflect(Component, Debug)]
 pub struct GameCamera;
 
+/// Shakes the camera after big hits. Trauma is added by each hit and wears off over time, and the
+/// camera moves further the more trauma there is, scaled by the screen shake setting.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct ScreenShake {
+    /// From `0` to `1`
+    pub trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.);
+    }
+}
+
 fn spawn_camera(mut commands: Commands) {
     commands
         .spawn((
@@ -42,24 +64,104 @@ fn spawn_camera(mut commands: Commands) {
         ))
         .insert(Tonemapping::TonyMcMapface)
         .insert(BloomSettings::NATURAL)
-        .insert(VolumetricFogSettings {
-            // This value is explicitly set to 0 since we have no environment map light
-            ambient_intensity: 0.0,
-            ..default()
-        });
+        .insert(volumetric_fog_settings());
 }
 
+fn volumetric_fog_settings() -> VolumetricFogSettings {
+    VolumetricFogSettings {
+        // This value is explicitly set to 0 since we have no environment map light
+        ambient_intensity: 0.0,
+        ..default()
+    }
+}
+
+/// System that turns bloom and fog on or off to match the settings
+fn apply_camera_settings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    query: Query<(Entity, Has<BloomSettings>, Has<VolumetricFogSettings>), With<GameCamera>>,
+) {
+    for (entity, has_bloom, has_fog) in query.iter() {
+        let mut camera = commands.entity(entity);
+        match (settings.bloom, has_bloom) {
+            (true, false) => {
+                camera.insert(BloomSettings::NATURAL);
+            }
+            (false, true) => {
+                camera.remove::<BloomSettings>();
+            }
+            _ => {}
+        }
+        match (settings.fog, has_fog) {
+            (true, false) => {
+                camera.insert(volumetric_fog_settings());
+            }
+            (false, true) => {
+                camera.remove::<VolumetricFogSettings>();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn reset_screen_shake(mut shake: ResMut<ScreenShake>) {
+    shake.trauma = 0.;
+}
+
+/// System that points the camera at the players from above the planet, pulling back so every
+/// player stays in view
 fn camera_follow_player(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut shake: ResMut<ScreenShake>,
+    mut rng: ResMut<GameRng>,
     mut query: Query<&mut Transform, With<GameCamera>>,
     player_query: Query<(&Transform, &Player), Without<GameCamera>>,
 ) {
+    let Some(center) = player_query
+        .iter()
+        .map(|(transform, _)| transform.translation.normalize())
+        .reduce(|acc, pos| acc + pos)
+        .and_then(|sum| sum.try_normalize())
+    else {
+        return;
+    };
+
+    // the camera is oriented using the average of the players' up directions
+    let up = player_query
+        .iter()
+        .map(|(_, player)| player.up)
+        .sum::<Vec3>()
+        .normalize_or(Vec3::Y);
+
+    // zoom out by the distance from the center to the furthest player
+    let spread = player_query
+        .iter()
+        .map(|(transform, _)| {
+            center.angle_between(transform.translation) * constants::PLANET_RADIUS
+        })
+        .fold(0., f32::max);
+    let distance = constants::PLANET_RADIUS
+        + constants::CAMERA_DISTANCE
+        + spread * constants::COOP_CAMERA_ZOOM;
+
+    // move the camera sideways by a random amount while shaking
+    let shake_distance =
+        shake.trauma.powi(2) * settings.screen_shake * constants::CAMERA_SHAKE_MAX_OFFSET;
+    let offset = if shake_distance > 0. {
+        let rng = rng.stream(RngStream::Cosmetics);
+        Vec3::new(
+            rng.gen_range(-1.0..=1.),
+            rng.gen_range(-1.0..=1.),
+            rng.gen_range(-1.0..=1.),
+        ) * shake_distance
+    } else {
+        Vec3::ZERO
+    };
+    shake.trauma = (shake.trauma - constants::CAMERA_SHAKE_DECAY * time.delta_seconds()).max(0.);
+
     for mut camera_transform in query.iter_mut() {
-        for (player_transform, player) in player_query.iter() {
-            *camera_transform = Transform::from_translation(
-                player_transform.translation.normalize()
-                    * (constants::PLANET_RADIUS + constants::CAMERA_DISTANCE),
-            )
-            .looking_at(player_transform.translation, player.up);
-        }
+        *camera_transform = Transform::from_translation(center * distance + offset)
+            .looking_at(center * constants::PLANET_RADIUS + offset, up);
     }
 }
@@ -83,6 +83,91 @@ impl Default for Collider {
     }
 }
 
+impl Collider {
+    /// Returns the radius of a sphere that roughly bounds the collider
+    pub fn bounding_radius(&self) -> f32 {
+        match self {
+            Collider::Sphere(r) => *r,
+            Collider::Cuboid(size) => size.max_element() / 2.,
+        }
+    }
+}
+
+/// A segment of a great circle around the center of the planet. It starts in the `start`
+/// direction and sweeps `angle` radians around `axis`.
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Debug)]
+pub struct GreatCircleArc {
+    pub start: Vec3,
+    pub axis: Vec3,
+    pub angle: f32,
+}
+
+impl GreatCircleArc {
+    /// Creates an arc starting at `start`, heading in the tangent `direction`, covering `angle`
+    /// radians.
+    pub fn new(start: Vec3, direction: Vec3, angle: f32) -> Self {
+        let start = start.normalize();
+        Self {
+            start,
+            axis: start.cross(direction).normalize(),
+            angle,
+        }
+    }
+
+    /// Returns the direction from the center of the planet to the end of the arc
+    pub fn end(&self) -> Vec3 {
+        Quat::from_axis_angle(self.axis, self.angle) * self.start
+    }
+
+    /// Returns the angle, in radians, between `point` and the closest point on the arc, as seen
+    /// from the center of the planet
+    pub fn angle_to(&self, point: Vec3) -> f32 {
+        let dir = point.normalize();
+
+        // project onto the plane of the great circle and measure how far along the arc it is
+        let projected = dir - self.axis * dir.dot(self.axis);
+        let along = f32::atan2(
+            self.start.cross(projected).dot(self.axis),
+            self.start.dot(projected),
+        );
+
+        if (0. ..=self.angle).contains(&along) {
+            // closest point is on the arc itself, so only the distance off the plane matters
+            dir.dot(self.axis).clamp(-1., 1.).asin().abs()
+        } else {
+            // closest point is one of the ends
+            dir.angle_between(self.start)
+                .min(dir.angle_between(self.end()))
+        }
+    }
+
+    /// Returns `true` if a collider at `translation` overlaps a band `half_width` either side
+    /// of the arc. Distances are measured along the surface at the collider's height.
+    pub fn intersects(&self, half_width: f32, collider: &Collider, translation: Vec3) -> bool {
+        let surface_distance = self.angle_to(translation) * translation.length();
+        surface_distance <= half_width + collider.bounding_radius()
+    }
+}
+
+/// Returns the entities whose colliders overlap a band `half_width` either side of the arc, and
+/// whose collision groups intersect `groups`.
+pub fn query_arc(
+    arc: &GreatCircleArc,
+    half_width: f32,
+    groups: &CollisionGroups,
+    query: &Query<(Entity, &Collider, &GlobalTransform, &CollisionGroups)>,
+) -> Vec<Entity> {
+    query
+        .iter()
+        .filter(|(_, collider, transform, other_groups)| {
+            groups.intersects(other_groups)
+                && arc.intersects(half_width, collider, transform.translation())
+        })
+        .map(|(entity, _, _, _)| entity)
+        .collect()
+}
+
 fn check_collisions(
     query: Query<(Entity, &Collider, &GlobalTransform, &CollisionGroups)>,
     mut collision_events: EventWriter<CollisionEvent>,
@@ -140,6 +225,8 @@ fn check_collisions(
 
 #[cfg(test)]
 mod tests {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
     use super::*;
 
     #[test]
@@ -230,4 +317,60 @@ mod tests {
             assert_eq!(result, test.equal, "{}", test.name);
         }
     }
+
+    #[test]
+    fn great_circle_arc_works() {
+        // quarter circle from +Z towards +Y
+        let arc = GreatCircleArc::new(Vec3::Z, Vec3::Y, FRAC_PI_2);
+        assert!(arc.end().abs_diff_eq(Vec3::Y, 1e-5));
+
+        struct TestCase {
+            name: String,
+            point: Vec3,
+            expected_angle: f32,
+        }
+        let test_cases = [
+            TestCase {
+                name: "start of arc".to_string(),
+                point: Vec3::Z * 10.,
+                expected_angle: 0.,
+            },
+            TestCase {
+                name: "middle of arc".to_string(),
+                point: Vec3::new(0., 1., 1.),
+                expected_angle: 0.,
+            },
+            TestCase {
+                name: "beside the arc".to_string(),
+                point: Quat::from_rotation_y(FRAC_PI_4) * Vec3::Z,
+                expected_angle: FRAC_PI_4,
+            },
+            TestCase {
+                name: "behind the start".to_string(),
+                point: Vec3::NEG_Y,
+                expected_angle: FRAC_PI_2,
+            },
+            TestCase {
+                name: "past the end".to_string(),
+                point: Vec3::NEG_Z,
+                expected_angle: FRAC_PI_2,
+            },
+        ];
+        for test in test_cases {
+            let angle = arc.angle_to(test.point);
+            assert!(
+                (angle - test.expected_angle).abs() < 1e-5,
+                "{}: expected {}, got {}",
+                test.name,
+                test.expected_angle,
+                angle
+            );
+        }
+
+        // colliders are tested against the band around the arc
+        let radius = 10.;
+        let beside = Quat::from_rotation_y(0.1) * Vec3::Z * radius;
+        assert!(arc.intersects(0.5, &Collider::Sphere(0.6), beside));
+        assert!(!arc.intersects(0.5, &Collider::Sphere(0.4), beside));
+    }
 }
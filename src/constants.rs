@@ -17,7 +17,55 @@ pub const PLAYER_DEFAULT_SPEED: f32 = 5.;
 pub const PLAYER_DEFAULT_ATTRACTOR_RADIUS: f32 = 5.;
 pub const PLAYER_DEFAULT_DAMAGE: f32 = ENEMY_DEFAULT_HEALTH;
 pub const PLAYER_DEFAULT_COOLDOWN: f32 = 1.;
+/// Weapon cooldown can't be upgraded below this
+pub const PLAYER_MIN_COOLDOWN: f32 = 0.1;
+/// Move speed can't be reduced below this
+pub const PLAYER_MIN_SPEED: f32 = 1.;
 pub const PLAYER_DEFAULT_RECOVERY: f32 = 1.;
+/// Number of times the power ups offered on level up can be re-drawn
+pub const PLAYER_DEFAULT_REROLLS: u32 = 2;
+/// Number of times a level up can be skipped
+pub const PLAYER_DEFAULT_SKIPS: u32 = 2;
+/// Number of stats that can be removed from the power ups offered
+pub const PLAYER_DEFAULT_BANISHES: u32 = 1;
+/// Percentage of max health recovered when skipping a level up
+pub const SKIP_HEAL_PERCENT: f32 = 20.;
+/// Distance between players when they spawn in local co-op
+pub const COOP_SPAWN_SPACING: f32 = 3.;
+/// How much further the camera pulls back for each unit of distance between players in local co-op
+pub const COOP_CAMERA_ZOOM: f32 = 1.5;
+/// Furthest the camera moves while shaking at full trauma
+pub const CAMERA_SHAKE_MAX_OFFSET: f32 = 0.6;
+/// Trauma the camera shake loses each second
+pub const CAMERA_SHAKE_DECAY: f32 = 1.5;
+/// Camera shake trauma added when a player is hit
+pub const CAMERA_SHAKE_PLAYER_HIT: f32 = 0.35;
+/// Camera shake trauma added when a shockwave is released
+pub const CAMERA_SHAKE_SHOCKWAVE: f32 = 0.6;
+
+/// Distance the player moves along the surface when dashing
+pub const DASH_DISTANCE: f32 = 5.;
+/// How long the player is invulnerable after dashing
+pub const DASH_INVULNERABILITY_SECS: f32 = 0.3;
+/// Default number of dashes that can be stored up
+pub const DASH_DEFAULT_CHARGES: u32 = 1;
+/// Default time to recharge a dash
+pub const DASH_DEFAULT_COOLDOWN: f32 = 3.;
+/// Dash cooldown can't be upgraded below this
+pub const DASH_MIN_COOLDOWN: f32 = 0.5;
+
+/// Charge needed to use the ultimate ability
+pub const ULTIMATE_MAX_CHARGE: f32 = 100.;
+/// Charge gained for every enemy killed
+pub const ULTIMATE_CHARGE_PER_KILL: f32 = 0.5;
+/// Charge gained for every point of damage dealt to enemies
+pub const ULTIMATE_CHARGE_PER_DAMAGE: f32 = 0.01;
+/// Speed the shockwave travels along the surface
+pub const SHOCKWAVE_SPEED: f32 = 30.;
+/// Damage dealt by the shockwave, enough to kill any enemy
+pub const SHOCKWAVE_DAMAGE: f32 = 100000.;
+/// Height of the shockwave ring above the surface
+pub const SHOCKWAVE_HEIGHT: f32 = 0.5;
 
 pub const FOILAGE_COUNT: u16 = 400;
 pub const FOILAGE_HEIGHT: f32 = 0.2;
@@ -26,6 +74,13 @@ pub const WEAPON_LENGTH: f32 = 0.2;
 pub const WEAPON_THICKNESS: f32 = 0.5;
 pub const WEAPON_BASE_AMOUNT: u32 = 3;
 
+/// Blaster level at which it can evolve
+pub const BLASTER_MAX_LEVEL: u32 = 4;
+/// Orb level at which they can evolve
+pub const ORB_MAX_LEVEL: u32 = 4;
+/// Orbital projectile level at which they can evolve
+pub const ORBITAL_PROJECTILE_MAX_LEVEL: u32 = 3;
+
 /// Radius of projectiles
 pub const PROJECTILE_RADIUS: f32 = 0.2;
 /// Height above the ground
@@ -33,6 +88,26 @@ pub const PROJECTILE_HEIGHT: f32 = 0.3;
 /// Base speed
 pub const PROJECTILE_BASE_SPEED: f32 = 1.;
 
+/// Default number of full laps an orbital projectile makes before it expires
+pub const ORBITAL_PROJECTILE_DEFAULT_MAX_LAPS: u32 = 3;
+/// Damage multiplier applied to an orbital projectile on each completed lap
+pub const ORBITAL_PROJECTILE_LAP_DAMAGE_MULTIPLIER: f32 = 1.5;
+/// Size multiplier applied to an orbital projectile on each completed lap
+pub const ORBITAL_PROJECTILE_LAP_SIZE_MULTIPLIER: f32 = 1.25;
+
+/// Base length of laser beams, along the surface
+pub const LASER_BASE_LENGTH: f32 = 8.;
+/// Base width of laser beams
+pub const LASER_BASE_WIDTH: f32 = 0.4;
+/// Height of laser beams above the ground
+pub const LASER_HEIGHT: f32 = 0.5;
+/// Speed laser beams sweep around the player, in radians per second
+pub const LASER_ROTATION_SPEED: f32 = 0.5;
+/// How often laser beams damage the enemies they overlap
+pub const LASER_TICK_SECS: f32 = 0.25;
+/// Laser base damage per tick
+pub const LASER_BASE_DAMAGE: f32 = ENEMY_DEFAULT_HEALTH / 2.;
+
 /// Radius of orbs
 pub const ORB_RADIUS: f32 = 0.5;
 /// Orb cooldown before it can hit the same entity again
@@ -45,9 +120,94 @@ pub const ORB_ORBIT_RADIUS: f32 = 4.;
 pub const ORB_BASE_DAMAGE: f32 = ENEMY_DEFAULT_HEALTH * 2.;
 /// Orb base amount
 pub const ORB_BASE_AMOUNT: u32 = 1;
+/// Orb base number of rings
+pub const ORB_BASE_RINGS: u32 = 1;
+/// Distance between each ring of orbs
+pub const ORB_RING_SPACING: f32 = 2.;
+/// How often a ring of fire damages the enemies touching it
+pub const RING_OF_FIRE_TICK_SECS: f32 = ORB_COOLDOWN_SECS;
+
+/// Size of drones
+pub const DRONE_SIZE: f32 = 0.5;
+/// Height of drones above the ground
+pub const DRONE_HEIGHT: f32 = 1.5;
+/// How far from the player drones hover
+pub const DRONE_FOLLOW_DISTANCE: f32 = 3.;
+/// How quickly drones catch up with the player. Lower values make them lag further behind.
+pub const DRONE_FOLLOW_SPEED: f32 = 2.;
+/// Size of turrets
+pub const TURRET_SIZE: f32 = 0.8;
+/// How long a turret lasts before it breaks down
+pub const TURRET_LIFETIME_SECS: f32 = 10.;
+/// How often a new turret is placed
+pub const TURRET_PLACE_COOLDOWN_SECS: f32 = 5.;
+/// How far away drones and turrets can pick a target
+pub const ALLY_TARGET_RANGE: f32 = 10.;
 
 /// Radius of points
 pub const POINT_RADIUS: f32 = 0.25;
 
 /// Speed of objects towards an attractor
 pub const ATTRACTOR_SPEED: f32 = 20.;
+
+/// Directory native builds save progress to, relative to the working directory
+pub const SAVE_DIRECTORY: &str = "save";
+/// Enemies killed for each gold earned at the end of a run
+pub const META_KILLS_PER_GOLD: u32 = 10;
+/// Seconds survived for each gold earned at the end of a run
+pub const META_SECONDS_PER_GOLD: u32 = 10;
+/// Points collected for each gold earned at the end of a run
+pub const META_POINTS_PER_GOLD: u32 = 5;
+/// How long the toast shown when an achievement is unlocked stays on screen
+pub const ACHIEVEMENT_TOAST_SECS: f32 = 4.;
+
+/// Number of passive items a player can hold
+pub const PLAYER_ITEM_SLOTS: usize = 2;
+/// Chance of an item being offered in place of a stat power up on level up
+pub const ITEM_OFFER_CHANCE: f64 = 0.25;
+/// Health healed for each point collected while holding a siphon
+pub const ITEM_SIPHON_HEAL: f32 = 0.5;
+/// Every nth projectile fired while holding a sharpshooter is critical
+pub const ITEM_CRITICAL_EVERY: u32 = 10;
+/// Damage multiplier of critical projectiles
+pub const ITEM_CRITICAL_MULTIPLIER: f32 = 3.;
+/// Base damage of enemies exploding, scaled by the player's damage
+pub const ITEM_EXPLOSION_DAMAGE: f32 = PLAYER_DEFAULT_DAMAGE;
+/// Radius of enemies exploding
+pub const ITEM_EXPLOSION_RADIUS: f32 = 2.5;
+/// How long the flash of an explosion stays visible
+pub const ITEM_EXPLOSION_LIFETIME_SECS: f32 = 0.2;
+
+/// Number of runs kept on the leaderboard of each game mode
+pub const LEADERBOARD_SIZE: usize = 10;
+/// Leaderboard score for each enemy killed, on top of one per point collected
+pub const LEADERBOARD_SCORE_PER_KILL: u32 = 2;
+/// Leaderboard score for each second survived
+pub const LEADERBOARD_SCORE_PER_SECOND: u32 = 1;
+/// Leaderboard score for each level reached
+pub const LEADERBOARD_SCORE_PER_LEVEL: u32 = 25;
+/// Longest name that can be entered on the leaderboard
+pub const LEADERBOARD_NAME_LENGTH: usize = 12;
+/// Name used when none is entered
+pub const LEADERBOARD_DEFAULT_NAME: &str = "PLAYER";
+
+/// Amount each press of a volume setting changes the volume by
+pub const SETTINGS_VOLUME_STEP: f32 = 0.1;
+/// Amount each press of the screen shake setting changes it by
+pub const SETTINGS_SCREEN_SHAKE_STEP: f32 = 0.25;
+/// UI scales to choose from, in order
+pub const SETTINGS_UI_SCALES: [f32; 4] = [0.75, 1., 1.25, 1.5];
+
+/// Directory native builds write the record of each finished run to, relative to the working
+/// directory
+pub const RUN_RECORD_DIRECTORY: &str = "runs";
+/// File in [`RUN_RECORD_DIRECTORY`] run records are appended to, one line of JSON per run
+pub const RUN_RECORD_FILE: &str = "runs.jsonl";
+/// Seconds between each sample of the kills over time in a run record
+pub const RUN_RECORD_SAMPLE_SECS: f32 = 30.;
+
+/// Longest seed that can be typed on the main menu
+pub const SEED_INPUT_LENGTH: usize = 20;
+
+/// Lowest percent a power up can reduce a stat by once scaled by its rarity
+pub const POWER_UP_MIN_PERCENT: i32 = -30;
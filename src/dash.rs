@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{math::*, *};
+
+pub struct DashPlugin;
+
+impl Plugin for DashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                (recharge_dashes, handle_dash_input)
+                    .chain()
+                    .run_if(not_paused),
+                update_dash_on_stats_change,
+            )
+                .run_if(in_game),
+        );
+    }
+}
+
+/// Lets the player jump a fixed distance along the surface, and ignore damage for a short time
+/// afterwards. Dashes are stored as charges, which recharge one at a time.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct Dash {
+    pub charges: u32,
+    pub max_charges: u32,
+    /// Recharges a single dash
+    pub recharge_timer: Timer,
+    pub invulnerability_timer: Timer,
+}
+
+impl Dash {
+    pub fn new(max_charges: u32, cooldown: f32) -> Self {
+        let mut invulnerability_timer =
+            Timer::from_seconds(constants::DASH_INVULNERABILITY_SECS, TimerMode::Once);
+        // start out vulnerable
+        invulnerability_timer.tick(invulnerability_timer.duration());
+        Self {
+            charges: max_charges,
+            max_charges,
+            recharge_timer: Timer::from_seconds(cooldown, TimerMode::Repeating),
+            invulnerability_timer,
+        }
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerability_timer.finished()
+    }
+
+    /// Uses a charge and starts the invulnerability window. Returns `false` if no charges are left.
+    pub fn try_dash(&mut self) -> bool {
+        if self.charges == 0 {
+            return false;
+        }
+        // the recharge only starts counting once a charge has been used
+        if self.charges == self.max_charges {
+            self.recharge_timer.reset();
+        }
+        self.charges -= 1;
+        self.invulnerability_timer.reset();
+        true
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.invulnerability_timer.tick(delta);
+        if self.charges < self.max_charges {
+            self.recharge_timer.tick(delta);
+            if self.recharge_timer.just_finished() {
+                self.charges += 1;
+            }
+        }
+    }
+
+    /// Returns how far the next charge has recharged, from `0` to `1`
+    pub fn recharge_fraction(&self) -> f32 {
+        if self.charges >= self.max_charges {
+            1.
+        } else {
+            self.recharge_timer.fraction()
+        }
+    }
+}
+
+fn update_dash_on_stats_change(mut query: Query<(&mut Dash, &PlayerStats), Changed<PlayerStats>>) {
+    for (mut dash, stats) in query.iter_mut() {
+        dash.max_charges = stats.dash_charges;
+        dash.charges = dash.charges.min(stats.dash_charges);
+        dash.recharge_timer
+            .set_duration(Duration::from_secs_f32(stats.dash_cooldown));
+    }
+}
+
+fn recharge_dashes(time: Res<Time>, mut query: Query<&mut Dash>) {
+    for mut dash in query.iter_mut() {
+        dash.tick(time.delta());
+    }
+}
+
+/// System that moves the player a fixed distance in the direction they are moving, or up if they
+/// are standing still
+fn handle_dash_input(mut query: Query<(&mut Dash, &mut Transform, &mut Player, &PlayerInput)>) {
+    let angle = get_angle_for_arc_length(constants::DASH_DISTANCE, constants::PLANET_RADIUS);
+
+    for (mut dash, mut transform, mut player, input) in query.iter_mut() {
+        if !input.actions.just_pressed(InputAction::Dash) || !dash.try_dash() {
+            continue;
+        }
+
+        let input = get_movement_input(&input.actions).normalize_or(Vec2::Y);
+
+        let rot = get_movement_rotation(transform.translation, player.up, input, angle);
+        transform.rotate_around(Vec3::ZERO, rot);
+        player.up = (rot * player.up).normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dash_uses_and_recharges_charges() {
+        let mut dash = Dash::new(2, 1.);
+        assert!(!dash.is_invulnerable());
+
+        assert!(dash.try_dash());
+        assert!(dash.is_invulnerable());
+        assert!(dash.try_dash());
+        assert!(!dash.try_dash());
+        assert_eq!(dash.recharge_fraction(), 0.);
+
+        // invulnerability wears off before the next charge is ready
+        dash.tick(Duration::from_secs_f32(
+            constants::DASH_INVULNERABILITY_SECS,
+        ));
+        assert!(!dash.is_invulnerable());
+        assert_eq!(dash.charges, 0);
+
+        // charges come back one at a time
+        dash.tick(Duration::from_secs(1));
+        assert_eq!(dash.charges, 1);
+        dash.tick(Duration::from_secs(1));
+        assert_eq!(dash.charges, 2);
+        dash.tick(Duration::from_secs(1));
+        assert_eq!(dash.charges, 2);
+        assert_eq!(dash.recharge_fraction(), 1.);
+    }
+}
@@ -16,6 +16,7 @@ impl Plugin for EnemyPlugin {
         app.init_resource::<EnemyResources>()
             .init_resource::<EnemyStatsTimer>()
             .add_event::<SpawnEnemies>()
+            .add_event::<EnemyKilled>()
             .add_systems(
                 Update,
                 (
@@ -33,7 +34,15 @@ impl Plugin for EnemyPlugin {
 #[reflect(Default, Debug)]
 pub struct SpawnEnemies;
 
-#[derive(Component, Debug, Reflect)]
+/// Sent when an enemy dies, before it is de-spawned
+#[derive(Event, Debug)]
+pub struct EnemyKilled {
+    pub position: Vec3,
+    /// What dealt the killing blow, if it was a weapon
+    pub source: Option<DamageSource>,
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Default, Debug)]
 pub struct Enemy {
     damage: f32,
@@ -123,7 +132,10 @@ fn handle_spawn_events(
     player_query: Query<(&Player, &Transform)>,
     enemy_stats: Res<EnemyStats>,
 ) {
-    let (player, player_transform) = player_query.single();
+    // spawn around the first player still standing
+    let Some((player, player_transform)) = player_query.iter().min_by_key(|(p, _)| p.index) else {
+        return;
+    };
 
     // compute horizontal axis (cross product)
     let camera_up = player.up.normalize();
@@ -193,20 +205,24 @@ fn move_enemies(
     mut query: Query<(&Enemy, &mut Transform)>,
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
 ) {
-    let player_transform = player_query.single();
-
     for (enemy, mut transform) in query.iter_mut() {
+        // chase the nearest player
+        let Some(player_pos) = get_nearest_player_pos(
+            transform.translation,
+            player_query.iter().map(|t| t.translation),
+        ) else {
+            continue;
+        };
+
         // skip if close enough to player
-        let dist = transform.translation.distance(player_transform.translation);
+        let dist = transform.translation.distance(player_pos);
         if dist < constants::PLAYER_SIZE / 2. + constants::ENEMY_SIZE / 2. {
             continue;
         }
 
         // get rotation towards player
-        let target_rot = Quat::from_rotation_arc(
-            transform.translation.normalize(),
-            player_transform.translation.normalize(),
-        );
+        let target_rot =
+            Quat::from_rotation_arc(transform.translation.normalize(), player_pos.normalize());
         let (axis, angle) = target_rot.to_axis_angle();
 
         // compute angle for movement speed
@@ -223,24 +239,40 @@ fn move_enemies(
 fn attack_players(
     time: Res<Time>,
     mut query: Query<(&mut Enemy, &Transform)>,
-    mut player_query: Query<(&mut Health, &Transform), (With<Player>, Without<Enemy>)>,
+    mut player_query: Query<
+        (Entity, &mut Health, &Transform, Option<&Dash>),
+        (With<Player>, Without<Enemy>),
+    >,
+    mut shake: ResMut<ScreenShake>,
+    mut damage_writer: EventWriter<DamageEvent>,
 ) {
-    let (mut player_health, player_transform) = player_query.single_mut();
-
     for (mut enemy, transform) in query.iter_mut() {
         enemy.cooldown_timer.tick(time.delta());
 
-        // skip if not close enough to player
-        let dist = transform.translation.distance(player_transform.translation);
         let attack_dist = constants::PLAYER_SIZE / 2. + constants::ENEMY_SIZE / 2.;
-        if dist > attack_dist {
-            continue;
-        }
+        for (player, mut player_health, player_transform, dash) in player_query.iter_mut() {
+            // skip if not close enough to player
+            let dist = transform.translation.distance(player_transform.translation);
+            if dist > attack_dist {
+                continue;
+            }
+
+            // the player can't be hurt while dashing
+            if dash.is_some_and(|dash| dash.is_invulnerable()) {
+                continue;
+            }
 
-        if !enemy.has_attacked || enemy.cooldown_timer.finished() {
-            enemy.has_attacked = true;
-            enemy.cooldown_timer.reset();
-            player_health.current -= enemy.damage;
+            if !enemy.has_attacked || enemy.cooldown_timer.finished() {
+                enemy.has_attacked = true;
+                enemy.cooldown_timer.reset();
+                player_health.damage(enemy.damage, DamageSource::Enemy);
+                damage_writer.send(DamageEvent {
+                    target: player,
+                    amount: enemy.damage,
+                    source: DamageSource::Enemy,
+                });
+                shake.add_trauma(constants::CAMERA_SHAKE_PLAYER_HIT);
+            }
         }
     }
 }
@@ -248,11 +280,16 @@ fn attack_players(
 fn handle_death_events(
     mut commands: Commands,
     mut events: EventReader<DeathEvent>,
-    query: Query<&GlobalTransform, With<Enemy>>,
+    query: Query<(&GlobalTransform, &Health), With<Enemy>>,
     mut score: ResMut<PlayerScore>,
+    mut killed_writer: EventWriter<EnemyKilled>,
 ) {
     for event in events.read() {
-        if let Ok(transform) = query.get(event.0) {
+        if let Ok((transform, health)) = query.get(event.0) {
+            killed_writer.send(EnemyKilled {
+                position: transform.translation(),
+                source: health.last_damage_source,
+            });
             // spawn a point bundle
             commands.spawn(PointBundle::new(transform.translation()));
             // track enemies killed in score
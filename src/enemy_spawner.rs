@@ -19,7 +19,7 @@ impl Plugin for EnemySpawnerPlugin {
     }
 }
 
-#[derive(Resource, Debug, Reflect)]
+#[derive(Resource, Debug, Clone, Reflect)]
 #[reflect(Resource, Default, Debug)]
 pub struct EnemySpawner {
     pub timer: Timer,
@@ -1,14 +1,14 @@
 use bevy::{color::palettes::css, prelude::*};
 use rand::{distributions::Uniform, Rng};
 
-use crate::{constants, game_state::*};
+use crate::{constants, game_state::*, reset_game_rng, GameRng, RngStream};
 
 pub struct FoilagePlugin;
 
 impl Plugin for FoilagePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<FoilageResources>()
-            .add_systems(OnEnter(AppState::Game), spawn_foilage);
+            .add_systems(OnEnter(AppState::Game), spawn_foilage.after(reset_game_rng));
     }
 }
 
@@ -60,13 +60,14 @@ fn spawn_foilage(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut foilage_resources: ResMut<FoilageResources>,
+    mut rng: ResMut<GameRng>,
 ) {
     let start = Vec3::new(0., 0., constants::PLANET_RADIUS);
     let mesh = foilage_resources.get_or_create_mesh(&mut meshes);
     let material = foilage_resources.get_or_create_material(&mut materials);
 
     let between = Uniform::from(0..=100);
-    let mut rng = rand::thread_rng();
+    let rng = rng.stream(RngStream::Cosmetics);
 
     for _ in 0..constants::FOILAGE_COUNT {
         let mut pos = Vec3::new(
@@ -1,11 +1,21 @@
+mod character;
 mod enemy_stats;
 mod enemy_stats_timer;
+mod evolution;
+mod game_rng;
 mod game_timer;
+mod leaderboard;
+mod meta_progress;
+mod player_count;
 mod player_score;
 mod player_stats;
 mod plugin;
+mod rarity;
+mod stat_modifier;
+mod xp_curve;
 
 pub use self::{
-    enemy_stats::*, enemy_stats_timer::*, game_timer::*, player_score::*, player_stats::*,
-    plugin::*,
+    character::*, enemy_stats::*, enemy_stats_timer::*, evolution::*, game_rng::*, game_timer::*,
+    leaderboard::*, meta_progress::*, player_count::*, player_score::*, player_stats::*, plugin::*,
+    rarity::*, stat_modifier::*, xp_curve::*,
 };
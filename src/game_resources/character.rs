@@ -0,0 +1,196 @@
+use std::fmt;
+
+use bevy::{color::palettes::css, prelude::*};
+
+use crate::{ModifierSource, PlayerStats, PowerUp, PowerUpValue, Rarity, Stat, WeaponKind};
+
+/// The characters the player can choose from before a run.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum CharacterKind {
+    #[default]
+    Pilot,
+    Mystic,
+    Engineer,
+    Juggernaut,
+}
+
+impl CharacterKind {
+    pub fn character(&self) -> &'static Character {
+        CHARACTERS
+            .iter()
+            .find(|character| character.kind == *self)
+            .expect("every character kind has an entry in CHARACTERS")
+    }
+}
+
+impl fmt::Display for CharacterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharacterKind::Pilot => write!(f, "Pilot"),
+            CharacterKind::Mystic => write!(f, "Mystic"),
+            CharacterKind::Engineer => write!(f, "Engineer"),
+            CharacterKind::Juggernaut => write!(f, "Juggernaut"),
+        }
+    }
+}
+
+/// Describes how a character starts a run, and the bonus they get as they level up.
+#[derive(Debug)]
+pub struct Character {
+    pub kind: CharacterKind,
+    pub description: &'static str,
+    /// Color of the player's mesh
+    pub color: Srgba,
+    /// The weapon the character starts with. Every player already has the blaster and orbs at
+    /// level 1, so only weapons the player doesn't have yet are given a level.
+    pub weapon: WeaponKind,
+    /// Applied on top of the default stats at the start of a run
+    pub starting_stats: &'static [PowerUp],
+    /// Applied every time the character levels up
+    pub passive: PowerUp,
+}
+
+pub static CHARACTERS: [Character; 4] = [
+    Character {
+        kind: CharacterKind::Pilot,
+        description: "A well-rounded fighter",
+        color: css::BLUE,
+        weapon: WeaponKind::Blaster,
+        starting_stats: &[],
+        passive: PowerUp {
+            stat: Stat::AttachDamage,
+            value: PowerUpValue::Percent(2),
+            rarity: Rarity::Common,
+        },
+    },
+    Character {
+        kind: CharacterKind::Mystic,
+        description: "Fragile, but surrounded by fast orbs",
+        color: css::MEDIUM_PURPLE,
+        weapon: WeaponKind::Orb,
+        starting_stats: &[
+            PowerUp {
+                stat: Stat::OrbSpeed,
+                value: PowerUpValue::Percent(20),
+                rarity: Rarity::Common,
+            },
+            PowerUp {
+                stat: Stat::MaxHealth,
+                value: PowerUpValue::Percent(-20),
+                rarity: Rarity::Common,
+            },
+        ],
+        passive: PowerUp {
+            stat: Stat::AtackSize,
+            value: PowerUpValue::Percent(2),
+            rarity: Rarity::Common,
+        },
+    },
+    Character {
+        kind: CharacterKind::Engineer,
+        description: "Collects points from further away",
+        color: css::ORANGE,
+        weapon: WeaponKind::OrbitalProjectile,
+        starting_stats: &[PowerUp {
+            stat: Stat::PickupRadius,
+            value: PowerUpValue::Percent(30),
+            rarity: Rarity::Common,
+        }],
+        passive: PowerUp {
+            stat: Stat::AttackCooldown,
+            value: PowerUpValue::Percent(-1),
+            rarity: Rarity::Common,
+        },
+    },
+    Character {
+        kind: CharacterKind::Juggernaut,
+        description: "Slow, but hard to bring down",
+        color: css::FOREST_GREEN,
+        weapon: WeaponKind::Blaster,
+        starting_stats: &[
+            PowerUp {
+                stat: Stat::MaxHealth,
+                value: PowerUpValue::Percent(30),
+                rarity: Rarity::Common,
+            },
+            PowerUp {
+                stat: Stat::Armor,
+                value: PowerUpValue::Percent(50),
+                rarity: Rarity::Common,
+            },
+            PowerUp {
+                stat: Stat::MoveSpeed,
+                value: PowerUpValue::Percent(-15),
+                rarity: Rarity::Common,
+            },
+        ],
+        passive: PowerUp {
+            stat: Stat::Recovery,
+            value: PowerUpValue::Percent(5),
+            rarity: Rarity::Common,
+        },
+    },
+];
+
+impl Character {
+    /// Returns the stats the character starts a run with
+    pub fn starting_stats(&self) -> PlayerStats {
+        let mut stats = PlayerStats::default();
+        if stats.get_weapon_level(self.weapon) == 0 {
+            stats.add_weapon_level(self.weapon, ModifierSource::Character);
+        }
+        for power_up in self.starting_stats.iter() {
+            stats.add_modifier(power_up.stat, power_up.value, ModifierSource::Character);
+        }
+        stats
+    }
+
+    pub fn passive_description(&self) -> String {
+        format!("{} every level", self.passive.description())
+    }
+}
+
+/// The characters chosen for the current run, in player order.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct SelectedCharacters(pub Vec<CharacterKind>);
+
+impl SelectedCharacters {
+    /// Returns the character chosen by a player, or the default if they haven't chosen one
+    pub fn get(&self, index: u32) -> CharacterKind {
+        self.0.get(index as usize).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, index: u32, character: CharacterKind) {
+        let index = index as usize;
+        if self.0.len() <= index {
+            self.0.resize(index + 1, CharacterKind::default());
+        }
+        self.0[index] = character;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_start_with_their_weapon_and_stats() {
+        let default_stats = PlayerStats::default();
+        for character in CHARACTERS.iter() {
+            assert_eq!(character.kind.character().kind, character.kind);
+
+            let stats = character.starting_stats();
+            assert_eq!(stats.get_weapon_level(character.weapon), 1);
+        }
+
+        // the blaster every character has isn't given an extra level
+        let stats = CharacterKind::Pilot.character().starting_stats();
+        assert_eq!(stats.attack_amount_extra, default_stats.attack_amount_extra);
+
+        let stats = CharacterKind::Juggernaut.character().starting_stats();
+        assert!(stats.max_health > default_stats.max_health);
+        assert!(stats.move_speed < default_stats.move_speed);
+    }
+}
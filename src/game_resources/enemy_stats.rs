@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::constants;
 
-#[derive(Resource, Debug, Reflect)]
+#[derive(Resource, Debug, Clone, Reflect)]
 #[reflect(Resource, Default, Debug)]
 pub struct EnemyStats {
     /// Movement speed multiplier
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::{constants, PlayerStats, Stat};
+
+/// The weapons the player can level up and evolve.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum WeaponKind {
+    Blaster,
+    Orb,
+    OrbitalProjectile,
+}
+
+impl WeaponKind {
+    pub const ALL: [Self; 3] = [Self::Blaster, Self::Orb, Self::OrbitalProjectile];
+
+    /// Returns the level at which the weapon can no longer be upgraded
+    pub fn max_level(&self) -> u32 {
+        match self {
+            WeaponKind::Blaster => constants::BLASTER_MAX_LEVEL,
+            WeaponKind::Orb => constants::ORB_MAX_LEVEL,
+            WeaponKind::OrbitalProjectile => constants::ORBITAL_PROJECTILE_MAX_LEVEL,
+        }
+    }
+}
+
+impl fmt::Display for WeaponKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeaponKind::Blaster => write!(f, "Blaster"),
+            WeaponKind::Orb => write!(f, "Orbs"),
+            WeaponKind::OrbitalProjectile => write!(f, "Orbital Projectiles"),
+        }
+    }
+}
+
+/// An evolved version of a weapon, with new behaviour.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum Evolution {
+    /// Orbs merge into permanent rings of fire that burn every enemy they touch
+    RingOfFire,
+    /// The blaster fires in 8 directions instead of 4
+    Nova,
+    /// Orbital projectiles keep circling the planet at full power instead of expiring
+    PerpetualOrbit,
+}
+
+impl Evolution {
+    pub fn description(&self) -> String {
+        match self {
+            Evolution::RingOfFire => "Orbs become permanent rings of fire".to_string(),
+            Evolution::Nova => "Blaster fires in 8 directions".to_string(),
+            Evolution::PerpetualOrbit => {
+                "Orbital projectiles never expire once at full power".to_string()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Evolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Evolution::RingOfFire => write!(f, "Ring of Fire"),
+            Evolution::Nova => write!(f, "Nova"),
+            Evolution::PerpetualOrbit => write!(f, "Perpetual Orbit"),
+        }
+    }
+}
+
+/// Describes when an [`Evolution`] becomes available: `weapon` must be at max level, and `stat`
+/// must have reached `threshold`.
+#[derive(Debug, Copy, Clone)]
+pub struct EvolutionRecipe {
+    pub evolution: Evolution,
+    pub weapon: WeaponKind,
+    pub stat: Stat,
+    pub threshold: f32,
+}
+
+pub const EVOLUTION_RECIPES: [EvolutionRecipe; 3] = [
+    EvolutionRecipe {
+        evolution: Evolution::RingOfFire,
+        weapon: WeaponKind::Orb,
+        stat: Stat::AtackSize,
+        threshold: 150.,
+    },
+    EvolutionRecipe {
+        evolution: Evolution::Nova,
+        weapon: WeaponKind::Blaster,
+        stat: Stat::ProjectilePassthrough,
+        threshold: 3.,
+    },
+    EvolutionRecipe {
+        evolution: Evolution::PerpetualOrbit,
+        weapon: WeaponKind::OrbitalProjectile,
+        stat: Stat::OrbitalMaxLaps,
+        threshold: 5.,
+    },
+];
+
+impl EvolutionRecipe {
+    /// Returns `true` if the recipe's requirements are met and the evolution has not been taken
+    pub fn is_available(&self, stats: &PlayerStats) -> bool {
+        !stats.has_evolution(self.evolution)
+            && stats.get_weapon_level(self.weapon) >= self.weapon.max_level()
+            && stats.get_stat_value(self.stat) >= self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ModifierSource, PowerUpValue};
+
+    use super::*;
+
+    /// Returns stats with the recipe's weapon at max level and its stat at the threshold, if asked
+    fn stats_for(recipe: &EvolutionRecipe, max_level: bool, threshold: bool) -> PlayerStats {
+        let mut stats = PlayerStats::default();
+        if max_level {
+            while stats.get_weapon_level(recipe.weapon) < recipe.weapon.max_level() {
+                stats.add_weapon_level(recipe.weapon, ModifierSource::PowerUp);
+            }
+        }
+        if threshold {
+            let value = match recipe.stat.get_random_range()[0] {
+                PowerUpValue::Amount(_) => PowerUpValue::Amount(1),
+                PowerUpValue::Percent(_) => PowerUpValue::Percent(10),
+            };
+            while stats.get_stat_value(recipe.stat) < recipe.threshold {
+                stats.add_modifier(recipe.stat, value, ModifierSource::PowerUp);
+            }
+        }
+        stats
+    }
+
+    #[test]
+    fn evolution_requires_max_level_and_stat_threshold() {
+        struct TestCase {
+            name: &'static str,
+            max_level: bool,
+            threshold: bool,
+            expected: bool,
+        }
+        let test_cases = [
+            TestCase {
+                name: "neither",
+                max_level: false,
+                threshold: false,
+                expected: false,
+            },
+            TestCase {
+                name: "max level alone",
+                max_level: true,
+                threshold: false,
+                expected: false,
+            },
+            TestCase {
+                name: "stat threshold alone",
+                max_level: false,
+                threshold: true,
+                expected: false,
+            },
+            TestCase {
+                name: "max level and stat threshold",
+                max_level: true,
+                threshold: true,
+                expected: true,
+            },
+        ];
+        for recipe in EVOLUTION_RECIPES.iter() {
+            for test in test_cases.iter() {
+                let stats = stats_for(recipe, test.max_level, test.threshold);
+                assert_eq!(
+                    recipe.is_available(&stats),
+                    test.expected,
+                    "{:?}: {}",
+                    recipe.evolution,
+                    test.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn evolution_is_not_offered_again_once_taken() {
+        for recipe in EVOLUTION_RECIPES.iter() {
+            let mut stats = stats_for(recipe, true, true);
+            assert_eq!(stats.get_available_evolutions(), vec![recipe.evolution]);
+
+            stats.add_evolution(recipe.evolution);
+            assert!(!recipe.is_available(&stats));
+            assert!(stats.get_available_evolutions().is_empty());
+        }
+    }
+}
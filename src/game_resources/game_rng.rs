@@ -0,0 +1,158 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+/// A separate stream of random numbers for each part of the game, so rolls in one never shift the
+/// rolls in another. For example, cosmetic rolls that depend on the frame rate don't change which
+/// power ups are offered.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum RngStream {
+    /// Which items are offered
+    Loot,
+    /// Which power ups are offered, and their rarity
+    PowerUps,
+    /// Anything that doesn't change the game, such as foilage and camera shake
+    Cosmetics,
+}
+
+impl RngStream {
+    pub const ALL: [Self; 3] = [Self::Loot, Self::PowerUps, Self::Cosmetics];
+}
+
+/// Every random number in a run comes from here, so a run can be reproduced from its seed.
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    seed: u64,
+    streams: [ChaCha12Rng; RngStream::ALL.len()],
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: RngStream::ALL.map(|stream| {
+                // spread the streams apart, each one is expanded into a full seed by `ChaCha12Rng`
+                ChaCha12Rng::seed_from_u64(
+                    seed ^ (stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                )
+            }),
+        }
+    }
+
+    /// Returns the streams of `seed`, moved on to `positions` from [`GameRng::positions`]
+    pub fn with_positions(seed: u64, positions: &[u128]) -> Self {
+        let mut rng = Self::new(seed);
+        for (stream, &position) in rng.streams.iter_mut().zip(positions) {
+            stream.set_word_pos(position);
+        }
+        rng
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns how far each stream has got, so a saved run can carry on with the same rolls
+    pub fn positions(&self) -> Vec<u128> {
+        self.streams
+            .iter()
+            .map(|stream| stream.get_word_pos())
+            .collect()
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut ChaCha12Rng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+/// Seed entered on the main menu for the next runs, or `None` to pick a random seed for each run
+#[derive(Resource, Default, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Default, Debug, PartialEq)]
+pub struct ChosenSeed(pub Option<u64>);
+
+impl ChosenSeed {
+    /// Returns the seed for text typed on the main menu. A number is used as is, any other text
+    /// is hashed into a seed, and empty text means a random seed.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        if text.is_empty() {
+            return Self(None);
+        }
+        Self(Some(text.parse().unwrap_or_else(|_| {
+            // FNV-1a, so the same text gives the same seed on every build
+            text.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3)
+            })
+        })))
+    }
+}
+
+impl fmt::Display for ChosenSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(seed) => write!(f, "{}", seed),
+            None => write!(f, "Random"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn streams_are_reproducible_and_independent() {
+        let rolls = |rng: &mut GameRng, stream| -> Vec<u32> {
+            (0..10).map(|_| rng.stream(stream).gen()).collect()
+        };
+
+        let mut rng = GameRng::new(1234);
+        let power_ups = rolls(&mut rng, RngStream::PowerUps);
+        assert_eq!(
+            power_ups,
+            rolls(&mut GameRng::new(1234), RngStream::PowerUps)
+        );
+        assert_ne!(
+            power_ups,
+            rolls(&mut GameRng::new(4321), RngStream::PowerUps)
+        );
+
+        // cosmetic rolls in between don't shift the power up rolls
+        let mut rng = GameRng::new(1234);
+        rolls(&mut rng, RngStream::Cosmetics);
+        assert_eq!(power_ups, rolls(&mut rng, RngStream::PowerUps));
+
+        // a stream moved on to a saved position carries on with the same rolls
+        let mut saved = GameRng::with_positions(1234, &rng.positions());
+        assert_eq!(
+            rolls(&mut rng, RngStream::PowerUps),
+            rolls(&mut saved, RngStream::PowerUps)
+        );
+
+        // every stream gives different numbers
+        let mut rng = GameRng::new(1234);
+        let streams: Vec<Vec<u32>> = RngStream::ALL
+            .into_iter()
+            .map(|stream| rolls(&mut rng, stream))
+            .collect();
+        for (index, stream) in streams.iter().enumerate() {
+            assert!(!streams[index + 1..].contains(stream));
+        }
+
+        assert_eq!(ChosenSeed::parse(" 42 "), ChosenSeed(Some(42)));
+        assert_eq!(ChosenSeed::parse(""), ChosenSeed(None));
+        assert_eq!(ChosenSeed::parse("planet"), ChosenSeed::parse("planet"));
+        assert_ne!(ChosenSeed::parse("planet"), ChosenSeed::parse("planets"));
+    }
+}
@@ -1,20 +1,24 @@
 use bevy::{prelude::*, time::Stopwatch};
 
-#[derive(Resource, Default, Debug, Reflect)]
+#[derive(Resource, Default, Debug, Clone, Reflect)]
 #[reflect(Resource, Default, Debug)]
 pub struct GameTimer(pub Stopwatch);
 
 impl GameTimer {
     pub fn to_time_string(&self) -> String {
-        let elapsed_secs = self.0.elapsed().as_secs();
-        let seconds = elapsed_secs % 60;
-        let minutes = (elapsed_secs / 60) % 60;
-        let hours = (elapsed_secs / 60) / 60;
+        format_time(self.0.elapsed().as_secs())
+    }
+}
+
+/// Formats a number of seconds as `mm:ss`, or `hh:mm:ss` once it reaches an hour
+pub fn format_time(elapsed_secs: u64) -> String {
+    let seconds = elapsed_secs % 60;
+    let minutes = (elapsed_secs / 60) % 60;
+    let hours = (elapsed_secs / 60) / 60;
 
-        if hours > 0 {
-            format!("{:0>2}:{:0>2}:{:0>2}", hours, minutes, seconds)
-        } else {
-            format!("{:0>2}:{:0>2}", minutes, seconds)
-        }
+    if hours > 0 {
+        format!("{:0>2}:{:0>2}:{:0>2}", hours, minutes, seconds)
+    } else {
+        format!("{:0>2}:{:0>2}", minutes, seconds)
     }
 }
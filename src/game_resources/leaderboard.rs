@@ -0,0 +1,148 @@
+use std::{collections::HashMap, fmt};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{constants, GameTimer, PlayerScore};
+
+/// Runs are ranked separately for each game mode.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum GameMode {
+    #[default]
+    Solo,
+    Coop,
+}
+
+impl GameMode {
+    pub const ALL: [Self; 2] = [Self::Solo, Self::Coop];
+
+    pub fn from_player_count(count: u32) -> Self {
+        if count > 1 {
+            Self::Coop
+        } else {
+            Self::Solo
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Solo => write!(f, "Solo"),
+            GameMode::Coop => write!(f, "Co-op"),
+        }
+    }
+}
+
+/// A finished run on the leaderboard.
+#[derive(Default, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Default, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u32,
+    pub seconds: u64,
+    pub points: u32,
+    pub kills: u32,
+    pub level: u32,
+}
+
+impl LeaderboardEntry {
+    /// Returns the entry for a finished run, with the score given by:
+    ///
+    /// `points + kills * KILL + seconds survived * SECOND + level * LEVEL`
+    ///
+    /// using the `LEADERBOARD_SCORE_PER_*` constants.
+    pub fn new(name: impl Into<String>, score: &PlayerScore, timer: &GameTimer) -> Self {
+        let seconds = timer.0.elapsed().as_secs();
+        Self {
+            name: name.into(),
+            score: score.total_points
+                + score.enemies_killed * constants::LEADERBOARD_SCORE_PER_KILL
+                + seconds as u32 * constants::LEADERBOARD_SCORE_PER_SECOND
+                + score.level * constants::LEADERBOARD_SCORE_PER_LEVEL,
+            seconds,
+            points: score.total_points,
+            kills: score.enemies_killed,
+            level: score.level,
+        }
+    }
+}
+
+/// The best runs of each game mode, highest score first. Saved to
+/// [`GameStorage`](crate::GameStorage) under [`Leaderboard::STORAGE_KEY`].
+#[derive(Resource, Default, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource, Default, Debug)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub modes: HashMap<GameMode, Vec<LeaderboardEntry>>,
+}
+
+impl Leaderboard {
+    pub const STORAGE_KEY: &'static str = "leaderboard";
+
+    pub fn entries(&self, mode: GameMode) -> &[LeaderboardEntry] {
+        self.modes.get(&mode).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns the index a score would be placed at, or `None` if it doesn't make the leaderboard.
+    /// Ties go below the existing entries.
+    pub fn rank_for(&self, mode: GameMode, score: u32) -> Option<usize> {
+        let rank = self
+            .entries(mode)
+            .iter()
+            .position(|entry| entry.score < score)
+            .unwrap_or(self.entries(mode).len());
+        (rank < constants::LEADERBOARD_SIZE).then_some(rank)
+    }
+
+    /// Adds an entry, dropping the lowest entry if the leaderboard is full. Returns the index it was
+    /// placed at, or `None` if it didn't make the leaderboard.
+    pub fn insert(&mut self, mode: GameMode, entry: LeaderboardEntry) -> Option<usize> {
+        let rank = self.rank_for(mode, entry.score)?;
+        let entries = self.modes.entry(mode).or_default();
+        entries.insert(rank, entry);
+        entries.truncate(constants::LEADERBOARD_SIZE);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaderboard_keeps_top_scores_per_mode() {
+        let entry = |name: &str, score: u32| LeaderboardEntry {
+            name: name.to_string(),
+            score,
+            ..default()
+        };
+        let mut leaderboard = Leaderboard::default();
+        for score in 1..=constants::LEADERBOARD_SIZE as u32 {
+            leaderboard.insert(GameMode::Solo, entry("low", score * 10));
+        }
+        let best = constants::LEADERBOARD_SIZE as u32 * 10;
+        assert_eq!(leaderboard.entries(GameMode::Solo)[0].score, best);
+
+        // a tie goes below the existing entry
+        assert_eq!(
+            leaderboard.insert(GameMode::Solo, entry("tie", best)),
+            Some(1)
+        );
+        assert_eq!(
+            leaderboard.entries(GameMode::Solo).len(),
+            constants::LEADERBOARD_SIZE
+        );
+
+        // too low to make the full leaderboard
+        assert_eq!(leaderboard.rank_for(GameMode::Solo, 5), None);
+        assert_eq!(leaderboard.insert(GameMode::Solo, entry("worst", 5)), None);
+
+        // other modes are ranked separately
+        assert_eq!(
+            leaderboard.insert(GameMode::Coop, entry("co-op", 5)),
+            Some(0)
+        );
+    }
+}
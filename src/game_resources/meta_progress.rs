@@ -0,0 +1,167 @@
+use std::{collections::HashMap, fmt};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants, CharacterKind, GameTimer, ModifierSource, PlayerScore, PlayerStats, PowerUp,
+    PowerUpValue, Rarity, Stat,
+};
+
+/// A permanent bonus bought in the shop with gold, applied to every player at the start of a run.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum MetaUpgrade {
+    MaxHealth,
+    Damage,
+    MoveSpeed,
+    PickupRadius,
+    Luck,
+    Reroll,
+}
+
+impl MetaUpgrade {
+    pub const ALL: [Self; 6] = [
+        Self::MaxHealth,
+        Self::Damage,
+        Self::MoveSpeed,
+        Self::PickupRadius,
+        Self::Luck,
+        Self::Reroll,
+    ];
+
+    /// Returns the bonus given by each level of the upgrade
+    pub fn power_up(&self) -> PowerUp {
+        let (stat, value) = match self {
+            MetaUpgrade::MaxHealth => (Stat::MaxHealth, PowerUpValue::Percent(10)),
+            MetaUpgrade::Damage => (Stat::AttachDamage, PowerUpValue::Percent(5)),
+            MetaUpgrade::MoveSpeed => (Stat::MoveSpeed, PowerUpValue::Percent(5)),
+            MetaUpgrade::PickupRadius => (Stat::PickupRadius, PowerUpValue::Percent(10)),
+            MetaUpgrade::Luck => (Stat::Luck, PowerUpValue::Percent(10)),
+            MetaUpgrade::Reroll => (Stat::Reroll, PowerUpValue::Amount(1)),
+        };
+        PowerUp {
+            stat,
+            value,
+            rarity: Rarity::Common,
+        }
+    }
+
+    pub fn max_level(&self) -> u32 {
+        match self {
+            MetaUpgrade::Reroll => 3,
+            _ => 5,
+        }
+    }
+
+    /// Returns the price of the first level. Each level after that costs as much again.
+    pub fn base_cost(&self) -> u32 {
+        match self {
+            MetaUpgrade::MaxHealth | MetaUpgrade::MoveSpeed | MetaUpgrade::PickupRadius => 10,
+            MetaUpgrade::Damage | MetaUpgrade::Luck => 15,
+            MetaUpgrade::Reroll => 25,
+        }
+    }
+}
+
+impl fmt::Display for MetaUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaUpgrade::MaxHealth => write!(f, "Vitality"),
+            MetaUpgrade::Damage => write!(f, "Might"),
+            MetaUpgrade::MoveSpeed => write!(f, "Swiftness"),
+            MetaUpgrade::PickupRadius => write!(f, "Magnetism"),
+            MetaUpgrade::Luck => write!(f, "Fortune"),
+            MetaUpgrade::Reroll => write!(f, "Second Thoughts"),
+        }
+    }
+}
+
+/// Progress kept between runs: the gold earned so far and the levels of the upgrades bought with
+/// it. Saved to [`GameStorage`](crate::GameStorage) under [`MetaProgress::STORAGE_KEY`].
+#[derive(Resource, Default, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource, Default, Debug)]
+#[serde(default)]
+pub struct MetaProgress {
+    pub gold: u32,
+    pub upgrades: HashMap<MetaUpgrade, u32>,
+}
+
+impl MetaProgress {
+    pub const STORAGE_KEY: &'static str = "meta_progress";
+
+    pub fn level(&self, upgrade: MetaUpgrade) -> u32 {
+        self.upgrades.get(&upgrade).copied().unwrap_or(0)
+    }
+
+    /// Returns the price of the next level of an upgrade, or `None` if it is already maxed out
+    pub fn cost(&self, upgrade: MetaUpgrade) -> Option<u32> {
+        let level = self.level(upgrade);
+        (level < upgrade.max_level()).then(|| upgrade.base_cost() * (level + 1))
+    }
+
+    /// Buys the next level of an upgrade. Returns `false` if it is maxed out or there isn't enough
+    /// gold.
+    pub fn try_buy(&mut self, upgrade: MetaUpgrade) -> bool {
+        let Some(cost) = self.cost(upgrade).filter(|cost| *cost <= self.gold) else {
+            return false;
+        };
+        self.gold -= cost;
+        *self.upgrades.entry(upgrade).or_insert(0) += 1;
+        true
+    }
+
+    /// Returns the stats a character starts a run with, including every upgrade bought
+    pub fn starting_stats(&self, character: CharacterKind) -> PlayerStats {
+        let mut stats = character.character().starting_stats();
+        for upgrade in MetaUpgrade::ALL {
+            let power_up = upgrade.power_up();
+            for _ in 0..self.level(upgrade) {
+                stats.add_modifier(power_up.stat, power_up.value, ModifierSource::Meta);
+            }
+        }
+        stats
+    }
+}
+
+/// Returns the gold earned by a run, from the enemies killed, the time survived and the points
+/// collected
+pub fn gold_for_run(score: &PlayerScore, timer: &GameTimer) -> u32 {
+    score.enemies_killed / constants::META_KILLS_PER_GOLD
+        + timer.0.elapsed().as_secs() as u32 / constants::META_SECONDS_PER_GOLD
+        + score.total_points / constants::META_POINTS_PER_GOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameStorage, MemoryStorage};
+
+    #[test]
+    fn upgrades_are_bought_saved_and_applied() {
+        let mut progress = MetaProgress {
+            gold: 25,
+            ..default()
+        };
+        let upgrade = MetaUpgrade::MaxHealth;
+        assert!(progress.try_buy(upgrade));
+        assert_eq!(progress.gold, 15);
+        assert_eq!(progress.cost(upgrade), Some(20));
+
+        // not enough gold for the second level
+        assert!(!progress.try_buy(upgrade));
+        assert_eq!(progress.level(upgrade), 1);
+
+        let storage = GameStorage(Box::new(MemoryStorage::default()));
+        storage.save_json(MetaProgress::STORAGE_KEY, &progress);
+        let loaded: MetaProgress = storage.load_json(MetaProgress::STORAGE_KEY).unwrap();
+        assert_eq!(loaded.gold, 15);
+        assert_eq!(loaded.level(upgrade), 1);
+
+        let character = CharacterKind::default();
+        let base = character.character().starting_stats();
+        let upgraded = loaded.starting_stats(character);
+        assert!((upgraded.max_health - base.max_health * 1.1).abs() < 0.001);
+        assert_eq!(upgraded.move_speed, base.move_speed);
+    }
+}
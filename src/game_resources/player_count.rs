@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// Number of players in the run. More than one for local co-op.
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct PlayerCount(pub u32);
+
+impl Default for PlayerCount {
+    fn default() -> Self {
+        Self(1)
+    }
+}
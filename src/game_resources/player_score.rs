@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
-#[derive(Resource, Debug, Reflect)]
+use crate::XpCurve;
+
+#[derive(Resource, Debug, Clone, Reflect)]
 #[reflect(Resource, Default, Debug)]
 pub struct PlayerScore {
     /// The players current level
@@ -23,7 +25,7 @@ impl Default for PlayerScore {
     fn default() -> Self {
         Self {
             level: 1,
-            next_level: 10,
+            next_level: XpCurve::default().points_for_level(1),
             current_points: 0,
             total_points: 0,
             enemies_killed: 0,
@@ -42,16 +44,51 @@ impl PlayerScore {
         self.enemies_killed += 1;
     }
 
-    /// Levels up if the current points have reached a new level.
-    /// Returns `true` if a new level was reached, `false` otherwise.
-    pub fn get_level_up(&mut self) -> bool {
-        if self.current_points >= self.next_level {
+    /// Levels up as many times as the current points allow, keeping the surplus towards the next
+    /// level. Returns the number of levels gained.
+    pub fn take_level_ups(&mut self, curve: &XpCurve) -> u32 {
+        let mut levels = 0;
+        while self.current_points >= self.next_level {
             self.current_points -= self.next_level;
             self.level += 1;
-            self.next_level *= 2;
-            true
-        } else {
-            false
+            self.next_level = curve.points_for_level(self.level);
+            levels += 1;
         }
+        levels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surplus_points_give_several_levels() {
+        let curve = XpCurve::Table(vec![10, 20, 40]);
+        let mut score = PlayerScore {
+            next_level: curve.points_for_level(1),
+            ..default()
+        };
+
+        score.add_points(5);
+        assert_eq!(score.take_level_ups(&curve), 0);
+
+        // enough for levels 1 and 2, with 5 points left over
+        score.add_points(30);
+        assert_eq!(score.take_level_ups(&curve), 2);
+        assert_eq!(score.level, 3);
+        assert_eq!(score.current_points, 5);
+        assert_eq!(score.next_level, 40);
+
+        // past the end of the table, levels keep growing by the last step
+        assert_eq!(curve.points_for_level(4), 60);
+        assert_eq!(curve.points_for_level(5), 80);
+
+        let curve = XpCurve::Exponential {
+            base: 10.,
+            growth: 2.,
+        };
+        assert_eq!(curve.points_for_level(1), 10);
+        assert_eq!(curve.points_for_level(3), 40);
     }
 }
@@ -1,18 +1,30 @@
 use std::fmt;
 
 use bevy::prelude::*;
-use rand::{distributions::Uniform, Rng};
+use rand::{seq::SliceRandom, Rng};
 
-use crate::constants;
+use crate::{
+    constants, Evolution, Item, ModifierSource, ModifierValue, Rarity, StatModifiers, WeaponKind,
+    EVOLUTION_RECIPES,
+};
 
-#[derive(Resource, Debug, Reflect)]
-#[reflect(Resource, Default, Debug)]
+/// The stats of a single player. Each player entity has its own.
+///
+/// Stat values are derived from the base value of each [`Stat`] and the [`StatModifiers`] applied
+/// to it, and are re-calculated whenever a modifier is added. Read the values from the fields, but
+/// change them by adding modifiers.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug)]
 pub struct PlayerStats {
     pub max_health: f32,
     pub recovery: f32,
     pub armor: f32,
 
     pub move_speed: f32,
+    /// Number of dashes that can be stored up
+    pub dash_charges: u32,
+    /// Time to recharge a dash
+    pub dash_cooldown: f32,
 
     pub damage_percent: f32,
     pub attack_size_percent: f32,
@@ -22,69 +34,178 @@ pub struct PlayerStats {
     pub projectile_speed: f32,
 
     pub extra_orbs: u32,
+    /// Distance of the innermost ring of orbs from the player
+    pub orb_orbit_radius: f32,
+    /// Speed orbs move around their ring
+    pub orb_speed: f32,
+    /// Number of rings of orbs around the player
+    pub orb_rings: u32,
+    /// Rotation direction of the innermost ring, flipped by each [`Stat::OrbReverse`]. Each
+    /// following ring alternates direction.
+    pub orb_direction: OrbDirection,
+
+    /// Number of orbital projectiles that can be in flight at once
+    pub orbital_projectiles: u32,
+    /// Number of laps an orbital projectile makes before it expires
+    pub orbital_max_laps: u32,
+
+    /// Number of laser beams sweeping around the player
+    pub laser_beams: u32,
+
+    /// Number of drones following the player
+    pub drones: u32,
+    /// Number of turrets that can be placed at once
+    pub turrets: u32,
 
     pub pickup_radius: f32,
+
+    /// Percentage that scales the odds of being offered rarer power ups
+    pub luck: f32,
+
+    /// Remaining number of times the power ups offered can be re-drawn
+    pub rerolls: u32,
+    /// Remaining number of times a level up can be skipped
+    pub skips: u32,
+    /// Remaining number of stats that can be banished
+    pub banishes: u32,
+    /// Stats that are no longer offered as power ups
+    pub banished: Vec<Stat>,
+
+    /// Weapons that aren't offered as power ups until an achievement unlocks them
+    pub locked_weapons: Vec<WeaponKind>,
+
+    /// Weapon evolutions that have been taken
+    pub evolutions: Vec<Evolution>,
+
+    /// Passive items held, up to [`constants::PLAYER_ITEM_SLOTS`]
+    pub items: Vec<Item>,
+
+    /// Modifiers the stat values are derived from
+    pub modifiers: StatModifiers,
+    /// Rerolls, skips and banishes used so far, subtracted from the amounts the modifiers give
+    pub rerolls_used: u32,
+    pub skips_used: u32,
+    pub banishes_used: u32,
 }
 
 impl Default for PlayerStats {
     fn default() -> Self {
-        Self {
-            max_health: 100.,
-            recovery: constants::PLAYER_DEFAULT_RECOVERY,
-            armor: 10.,
-            move_speed: constants::PLAYER_DEFAULT_SPEED,
-            damage_percent: 100.,
-            attack_size_percent: 100.,
-            attack_cooldown: constants::PLAYER_DEFAULT_COOLDOWN,
-            attack_amount_extra: 0,
-            pickup_radius: constants::PLAYER_DEFAULT_ATTRACTOR_RADIUS,
-            projectile_passthrough: 1,
-            extra_orbs: 0,
-            projectile_speed: 100.,
-        }
+        Self::from_modifiers(StatModifiers::default())
     }
 }
 
 impl PlayerStats {
+    /// Returns the stats derived from a set of modifiers
+    pub fn from_modifiers(modifiers: StatModifiers) -> Self {
+        let mut stats = Self {
+            max_health: 0.,
+            recovery: 0.,
+            armor: 0.,
+            move_speed: 0.,
+            dash_charges: 0,
+            dash_cooldown: 0.,
+            damage_percent: 0.,
+            attack_size_percent: 0.,
+            attack_cooldown: 0.,
+            attack_amount_extra: 0,
+            projectile_passthrough: 0,
+            projectile_speed: 0.,
+            extra_orbs: 0,
+            orb_orbit_radius: 0.,
+            orb_speed: 0.,
+            orb_rings: 0,
+            orb_direction: OrbDirection::default(),
+            orbital_projectiles: 0,
+            orbital_max_laps: 0,
+            laser_beams: 0,
+            drones: 0,
+            turrets: 0,
+            pickup_radius: 0.,
+            luck: 0.,
+            rerolls: 0,
+            skips: 0,
+            banishes: 0,
+            banished: Vec::new(),
+            locked_weapons: Vec::new(),
+            evolutions: Vec::new(),
+            items: Vec::new(),
+            modifiers,
+            rerolls_used: 0,
+            skips_used: 0,
+            banishes_used: 0,
+        };
+        stats.recalculate();
+        stats
+    }
+
+    /// Re-derives every stat value from the modifiers
+    pub fn recalculate(&mut self) {
+        let m = &self.modifiers;
+        self.max_health = m.get_value(Stat::MaxHealth);
+        self.recovery = m.get_value(Stat::Recovery);
+        self.armor = m.get_value(Stat::Armor);
+        self.move_speed = m.get_value(Stat::MoveSpeed);
+        self.dash_charges = m.get_count(Stat::DashCharges);
+        self.dash_cooldown = m.get_value(Stat::DashCooldown);
+        self.damage_percent = m.get_value(Stat::AttachDamage);
+        self.attack_size_percent = m.get_value(Stat::AtackSize);
+        self.attack_cooldown = m.get_value(Stat::AttackCooldown);
+        self.attack_amount_extra = m.get_count(Stat::AttackAmount);
+        self.pickup_radius = m.get_value(Stat::PickupRadius);
+        self.projectile_passthrough = m.get_count(Stat::ProjectilePassthrough);
+        self.extra_orbs = m.get_count(Stat::OrbCount);
+        self.projectile_speed = m.get_value(Stat::ProjectileSpeed);
+        self.orb_orbit_radius = m.get_value(Stat::OrbRadius);
+        self.orb_speed = m.get_value(Stat::OrbSpeed);
+        self.orb_rings = m.get_count(Stat::OrbRings);
+        self.orb_direction = if m.get_count(Stat::OrbReverse).is_multiple_of(2) {
+            OrbDirection::CounterClockwise
+        } else {
+            OrbDirection::Clockwise
+        };
+        self.orbital_projectiles = m.get_count(Stat::OrbitalProjectileAmount);
+        self.orbital_max_laps = m.get_count(Stat::OrbitalMaxLaps);
+        self.laser_beams = m.get_count(Stat::LaserBeam);
+        self.drones = m.get_count(Stat::DroneCount);
+        self.turrets = m.get_count(Stat::TurretCount);
+        self.luck = m.get_value(Stat::Luck);
+        self.rerolls = m.get_count(Stat::Reroll).saturating_sub(self.rerolls_used);
+        self.skips = m.get_count(Stat::Skip).saturating_sub(self.skips_used);
+        self.banishes = m.get_count(Stat::Banish).saturating_sub(self.banishes_used);
+    }
+
+    pub fn add_modifier(
+        &mut self,
+        stat: Stat,
+        value: impl Into<ModifierValue>,
+        source: ModifierSource,
+    ) {
+        self.modifiers.push(stat, value, source);
+        self.recalculate();
+    }
+
     pub fn add_power_up(&mut self, power_up: &PowerUp) {
-        match power_up.stat {
-            Stat::MaxHealth => {
-                self.max_health = power_up.value.add_f32(self.max_health);
-            }
-            Stat::Recovery => {
-                self.recovery = power_up.value.add_f32(self.recovery);
-            }
-            Stat::Armor => {
-                self.armor = power_up.value.add_f32(self.armor);
-            }
-            Stat::MoveSpeed => {
-                self.move_speed = power_up.value.add_f32(self.move_speed);
-            }
-            Stat::AttachDamage => {
-                self.damage_percent = power_up.value.add_f32(self.damage_percent);
-            }
-            Stat::AtackSize => {
-                self.attack_size_percent = power_up.value.add_f32(self.attack_size_percent);
-            }
-            Stat::AttackCooldown => {
-                self.attack_cooldown = power_up.value.add_f32(self.attack_cooldown);
-            }
-            Stat::AttackAmount => {
-                self.attack_amount_extra = power_up.value.add_u32(self.attack_amount_extra);
-            }
-            Stat::PickupRadius => {
-                self.pickup_radius = power_up.value.add_f32(self.pickup_radius);
-            }
-            Stat::ProjectilePassthrough => {
-                self.projectile_passthrough = power_up.value.add_u32(self.projectile_passthrough);
-            }
-            Stat::OrbCount => {
-                self.extra_orbs = power_up.value.add_u32(self.extra_orbs);
-            }
-            Stat::ProjectileSpeed => {
-                self.projectile_speed = power_up.value.add_f32(self.projectile_speed);
-            }
+        self.add_modifier(power_up.stat, power_up.value, ModifierSource::PowerUp);
+    }
+
+    /// Uses up a reroll. Returns `false` if none are left.
+    pub fn use_reroll(&mut self) -> bool {
+        if self.rerolls == 0 {
+            return false;
+        }
+        self.rerolls_used += 1;
+        self.recalculate();
+        true
+    }
+
+    /// Uses up a skip. Returns `false` if none are left.
+    pub fn use_skip(&mut self) -> bool {
+        if self.skips == 0 {
+            return false;
         }
+        self.skips_used += 1;
+        self.recalculate();
+        true
     }
 
     pub fn get_attack_size(&self, size: f32) -> f32 {
@@ -102,6 +223,164 @@ impl PlayerStats {
     pub fn get_attack_speed(&self, speed: f32) -> f32 {
         speed * self.projectile_speed / 100.
     }
+
+    /// Returns the current value of a stat
+    pub fn get_stat_value(&self, stat: Stat) -> f32 {
+        match stat {
+            Stat::MaxHealth => self.max_health,
+            Stat::Recovery => self.recovery,
+            Stat::Armor => self.armor,
+            Stat::MoveSpeed => self.move_speed,
+            Stat::DashCharges => self.dash_charges as f32,
+            Stat::DashCooldown => self.dash_cooldown,
+            Stat::AttachDamage => self.damage_percent,
+            Stat::AtackSize => self.attack_size_percent,
+            Stat::AttackCooldown => self.attack_cooldown,
+            Stat::AttackAmount => self.attack_amount_extra as f32,
+            Stat::PickupRadius => self.pickup_radius,
+            Stat::ProjectilePassthrough => self.projectile_passthrough as f32,
+            Stat::OrbCount => self.extra_orbs as f32,
+            Stat::ProjectileSpeed => self.projectile_speed,
+            Stat::OrbRadius => self.orb_orbit_radius,
+            Stat::OrbSpeed => self.orb_speed,
+            Stat::OrbRings => self.orb_rings as f32,
+            Stat::OrbReverse => self.modifiers.get_count(Stat::OrbReverse) as f32,
+            Stat::OrbitalProjectileAmount => self.orbital_projectiles as f32,
+            Stat::OrbitalMaxLaps => self.orbital_max_laps as f32,
+            Stat::LaserBeam => self.laser_beams as f32,
+            Stat::DroneCount => self.drones as f32,
+            Stat::TurretCount => self.turrets as f32,
+            Stat::Luck => self.luck,
+            Stat::Reroll => self.rerolls as f32,
+            Stat::Skip => self.skips as f32,
+            Stat::Banish => self.banishes as f32,
+        }
+    }
+
+    /// Returns the level of a weapon
+    pub fn get_weapon_level(&self, weapon: WeaponKind) -> u32 {
+        match weapon {
+            WeaponKind::Blaster => 1 + self.attack_amount_extra,
+            WeaponKind::Orb => constants::ORB_BASE_AMOUNT + self.extra_orbs,
+            WeaponKind::OrbitalProjectile => self.orbital_projectiles,
+        }
+    }
+
+    /// Adds a level to a weapon, ignoring its max level
+    pub fn add_weapon_level(&mut self, weapon: WeaponKind, source: ModifierSource) {
+        let stat = match weapon {
+            WeaponKind::Blaster => Stat::AttackAmount,
+            WeaponKind::Orb => Stat::OrbCount,
+            WeaponKind::OrbitalProjectile => Stat::OrbitalProjectileAmount,
+        };
+        self.add_modifier(stat, PowerUpValue::Amount(1), source);
+    }
+
+    /// Returns `false` if the stat levels up a weapon that is locked or has already reached max
+    /// level
+    pub fn can_upgrade(&self, stat: Stat) -> bool {
+        let Some(weapon) = stat.weapon() else {
+            return true;
+        };
+        !self.locked_weapons.contains(&weapon) && self.get_weapon_level(weapon) < weapon.max_level()
+    }
+
+    /// Removes a stat from the power ups offered, using up a banish. Returns `false` if none are left.
+    pub fn banish(&mut self, stat: Stat) -> bool {
+        if self.banishes == 0 {
+            return false;
+        }
+        self.banishes_used += 1;
+        if !self.banished.contains(&stat) {
+            self.banished.push(stat);
+        }
+        self.recalculate();
+        true
+    }
+
+    pub fn has_evolution(&self, evolution: Evolution) -> bool {
+        self.evolutions.contains(&evolution)
+    }
+
+    pub fn add_evolution(&mut self, evolution: Evolution) {
+        if !self.has_evolution(evolution) {
+            self.evolutions.push(evolution);
+        }
+    }
+
+    pub fn has_item(&self, item: Item) -> bool {
+        self.items.contains(&item)
+    }
+
+    /// Adds an item, unless it is already held or every slot is full
+    pub fn add_item(&mut self, item: Item) {
+        if !self.has_item(item) && self.items.len() < constants::PLAYER_ITEM_SLOTS {
+            self.items.push(item);
+        }
+    }
+
+    /// Returns the evolutions whose recipe requirements are currently met
+    pub fn get_available_evolutions(&self) -> Vec<Evolution> {
+        EVOLUTION_RECIPES
+            .iter()
+            .filter(|recipe| recipe.is_available(self))
+            .map(|recipe| recipe.evolution)
+            .collect()
+    }
+
+    /// Returns the number of orbs in each ring
+    pub fn get_orbs_per_ring(&self) -> u32 {
+        self.get_amount(self.extra_orbs + constants::ORB_BASE_AMOUNT)
+    }
+
+    /// Returns the distance from the player of the given ring of orbs
+    pub fn get_orb_ring_radius(&self, ring: u32) -> f32 {
+        self.orb_orbit_radius + ring as f32 * constants::ORB_RING_SPACING
+    }
+
+    /// Returns the rotation direction of the given ring of orbs
+    pub fn get_orb_ring_direction(&self, ring: u32) -> OrbDirection {
+        if ring.is_multiple_of(2) {
+            self.orb_direction
+        } else {
+            self.orb_direction.reversed()
+        }
+    }
+}
+
+/// Direction orbs rotate around the player, as seen from the camera.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum OrbDirection {
+    #[default]
+    CounterClockwise,
+    Clockwise,
+}
+
+impl OrbDirection {
+    pub fn reversed(&self) -> Self {
+        match self {
+            OrbDirection::CounterClockwise => OrbDirection::Clockwise,
+            OrbDirection::Clockwise => OrbDirection::CounterClockwise,
+        }
+    }
+
+    /// Returns the sign to apply to the orb's angle of rotation
+    pub fn sign(&self) -> f32 {
+        match self {
+            OrbDirection::CounterClockwise => 1.,
+            OrbDirection::Clockwise => -1.,
+        }
+    }
+}
+
+impl fmt::Display for OrbDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrbDirection::CounterClockwise => write!(f, "Counter-Clockwise"),
+            OrbDirection::Clockwise => write!(f, "Clockwise"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -109,10 +388,14 @@ impl PlayerStats {
 pub struct PowerUp {
     pub stat: Stat,
     pub value: PowerUpValue,
+    pub rarity: Rarity,
 }
 
 impl PowerUp {
     pub fn description(&self) -> String {
+        if self.stat == Stat::OrbReverse {
+            return "Reverse the direction orbs rotate".to_string();
+        }
         let prefix = if self.value.value() > 0 {
             "Increase"
         } else {
@@ -121,17 +404,39 @@ impl PowerUp {
         format!("{} {} by {}", prefix, self.stat, self.value)
     }
 
-    pub fn new_random() -> Self {
-        let stat = Stat::new_random();
-        let options = stat.get_random_range();
-        let value = if options.len() == 1 {
-            options[0]
-        } else {
-            let mut rng = rand::thread_rng();
-            let index = rng.gen_range(0..options.len());
-            options[index]
-        };
-        Self { stat, value }
+    /// Returns a random power up for a stat, with a rarity rolled using the player's luck
+    pub fn new_random(stat: Stat, luck: f32, rng: &mut impl Rng) -> Self {
+        let value = *stat
+            .get_random_range()
+            .choose(rng)
+            .expect("every stat has at least one value");
+        if !stat.scales_with_rarity() {
+            // rarity can't make the power up stronger, so it is always common
+            return Self {
+                stat,
+                value,
+                rarity: Rarity::Common,
+            };
+        }
+        let rarity = Rarity::new_random(luck, rng);
+        Self {
+            stat,
+            value: value.scaled(rarity.multiplier()),
+            rarity,
+        }
+    }
+
+    /// Returns up to `count` random power ups to offer a player, each for a different stat, skipping
+    /// banished stats and weapons that have reached max level
+    pub fn new_random_offers(stats: &PlayerStats, count: usize, rng: &mut impl Rng) -> Vec<Self> {
+        let stats_to_offer: Vec<Stat> = Stat::ALL
+            .into_iter()
+            .filter(|stat| stats.can_upgrade(*stat) && !stats.banished.contains(stat))
+            .collect();
+        stats_to_offer
+            .choose_multiple(rng, count)
+            .map(|stat| Self::new_random(*stat, stats.luck, rng))
+            .collect()
     }
 }
 
@@ -150,17 +455,22 @@ impl PowerUpValue {
         }
     }
 
-    pub fn add_f32(&self, value: f32) -> f32 {
-        match self {
-            PowerUpValue::Percent(val) => value + (value * *val as f32 / 100.),
-            PowerUpValue::Amount(val) => value + *val as f32,
-        }
-    }
-
-    pub fn add_u32(&self, value: u32) -> u32 {
+    /// Returns the value multiplied by `multiplier`. Reductions stop at
+    /// [`constants::POWER_UP_MIN_PERCENT`], and amounts are rounded up so every step of rarity
+    /// adds to them.
+    pub fn scaled(&self, multiplier: f32) -> Self {
         match self {
-            PowerUpValue::Percent(val) => value + (value as f32 * *val as f32 / 100.) as u32,
-            PowerUpValue::Amount(val) => value + *val as u32,
+            PowerUpValue::Percent(val) => {
+                let scaled = (*val as f32 * multiplier).round() as i32;
+                if *val < 0 {
+                    PowerUpValue::Percent(scaled.max(constants::POWER_UP_MIN_PERCENT.min(*val)))
+                } else {
+                    PowerUpValue::Percent(scaled)
+                }
+            }
+            PowerUpValue::Amount(val) => {
+                PowerUpValue::Amount((*val as f32 * multiplier).ceil() as i32)
+            }
         }
     }
 }
@@ -181,6 +491,8 @@ pub enum Stat {
     Recovery,
     Armor,
     MoveSpeed,
+    DashCharges,
+    DashCooldown,
     AttachDamage,
     AtackSize,
     AttackCooldown,
@@ -189,6 +501,19 @@ pub enum Stat {
     ProjectilePassthrough,
     OrbCount,
     ProjectileSpeed,
+    OrbRadius,
+    OrbSpeed,
+    OrbRings,
+    OrbReverse,
+    OrbitalProjectileAmount,
+    OrbitalMaxLaps,
+    LaserBeam,
+    DroneCount,
+    TurretCount,
+    Luck,
+    Reroll,
+    Skip,
+    Banish,
 }
 
 impl fmt::Display for Stat {
@@ -198,6 +523,8 @@ impl fmt::Display for Stat {
             Stat::Recovery => write!(f, "Recovery"),
             Stat::Armor => write!(f, "Armor"),
             Stat::MoveSpeed => write!(f, "Move Speed"),
+            Stat::DashCharges => write!(f, "Dash Charge"),
+            Stat::DashCooldown => write!(f, "Dash Cooldown"),
             Stat::AttachDamage => write!(f, "Damage"),
             Stat::AtackSize => write!(f, "Projectile Size"),
             Stat::AttackCooldown => write!(f, "Weapon Cooldown"),
@@ -206,16 +533,31 @@ impl fmt::Display for Stat {
             Stat::ProjectilePassthrough => write!(f, "Projectile Passthrough"),
             Stat::OrbCount => write!(f, "Orb"),
             Stat::ProjectileSpeed => write!(f, "Projectile Speed"),
+            Stat::OrbRadius => write!(f, "Orb Orbit Radius"),
+            Stat::OrbSpeed => write!(f, "Orb Speed"),
+            Stat::OrbRings => write!(f, "Orb Ring"),
+            Stat::OrbReverse => write!(f, "Orb Reversal"),
+            Stat::OrbitalProjectileAmount => write!(f, "Orbital Projectile"),
+            Stat::OrbitalMaxLaps => write!(f, "Orbital Laps"),
+            Stat::LaserBeam => write!(f, "Laser Beam"),
+            Stat::DroneCount => write!(f, "Drone"),
+            Stat::TurretCount => write!(f, "Turret"),
+            Stat::Luck => write!(f, "Luck"),
+            Stat::Reroll => write!(f, "Reroll"),
+            Stat::Skip => write!(f, "Skip"),
+            Stat::Banish => write!(f, "Banish"),
         }
     }
 }
 
 impl Stat {
-    const ALL: [Self; 12] = [
+    const ALL: [Self; 27] = [
         Self::MaxHealth,
         Self::Recovery,
         Self::Armor,
         Self::MoveSpeed,
+        Self::DashCharges,
+        Self::DashCooldown,
         Self::AttachDamage,
         Self::AtackSize,
         Self::AttackCooldown,
@@ -224,8 +566,84 @@ impl Stat {
         Self::ProjectilePassthrough,
         Self::OrbCount,
         Self::ProjectileSpeed,
+        Self::OrbRadius,
+        Self::OrbSpeed,
+        Self::OrbRings,
+        Self::OrbReverse,
+        Self::OrbitalProjectileAmount,
+        Self::OrbitalMaxLaps,
+        Self::LaserBeam,
+        Self::DroneCount,
+        Self::TurretCount,
+        Self::Luck,
+        Self::Reroll,
+        Self::Skip,
+        Self::Banish,
     ];
 
+    /// Returns the value of the stat before any modifiers
+    pub fn base_value(&self) -> f32 {
+        match self {
+            Stat::MaxHealth => 100.,
+            Stat::Recovery => constants::PLAYER_DEFAULT_RECOVERY,
+            Stat::Armor => 10.,
+            Stat::MoveSpeed => constants::PLAYER_DEFAULT_SPEED,
+            Stat::DashCharges => constants::DASH_DEFAULT_CHARGES as f32,
+            Stat::DashCooldown => constants::DASH_DEFAULT_COOLDOWN,
+            Stat::AttachDamage => 100.,
+            Stat::AtackSize => 100.,
+            Stat::AttackCooldown => constants::PLAYER_DEFAULT_COOLDOWN,
+            Stat::AttackAmount => 0.,
+            Stat::PickupRadius => constants::PLAYER_DEFAULT_ATTRACTOR_RADIUS,
+            Stat::ProjectilePassthrough => 1.,
+            Stat::OrbCount => 0.,
+            Stat::ProjectileSpeed => 100.,
+            Stat::OrbRadius => constants::ORB_ORBIT_RADIUS,
+            Stat::OrbSpeed => constants::ORB_MOVEMENT_SPEED,
+            Stat::OrbRings => constants::ORB_BASE_RINGS as f32,
+            Stat::OrbReverse => 0.,
+            Stat::OrbitalProjectileAmount => 0.,
+            Stat::OrbitalMaxLaps => constants::ORBITAL_PROJECTILE_DEFAULT_MAX_LAPS as f32,
+            Stat::LaserBeam => 0.,
+            Stat::DroneCount => 0.,
+            Stat::TurretCount => 0.,
+            Stat::Luck => 100.,
+            Stat::Reroll => constants::PLAYER_DEFAULT_REROLLS as f32,
+            Stat::Skip => constants::PLAYER_DEFAULT_SKIPS as f32,
+            Stat::Banish => constants::PLAYER_DEFAULT_BANISHES as f32,
+        }
+    }
+
+    /// Returns `false` for stats where only whether a power up was taken matters, not its value,
+    /// and for weapon levels, which go up one at a time to their max level
+    pub fn scales_with_rarity(&self) -> bool {
+        !matches!(self, Stat::OrbReverse) && self.weapon().is_none()
+    }
+
+    /// Returns the weapon the stat levels up
+    pub fn weapon(&self) -> Option<WeaponKind> {
+        match self {
+            Stat::AttackAmount => Some(WeaponKind::Blaster),
+            Stat::OrbCount => Some(WeaponKind::Orb),
+            Stat::OrbitalProjectileAmount => Some(WeaponKind::OrbitalProjectile),
+            _ => None,
+        }
+    }
+
+    /// Returns the minimum and maximum value of the stat after modifiers
+    pub fn limits(&self) -> (f32, f32) {
+        match self {
+            Stat::MaxHealth => (1., f32::MAX),
+            Stat::MoveSpeed => (constants::PLAYER_MIN_SPEED, f32::MAX),
+            Stat::DashCooldown => (constants::DASH_MIN_COOLDOWN, f32::MAX),
+            Stat::AttachDamage | Stat::AtackSize | Stat::ProjectileSpeed => (10., f32::MAX),
+            Stat::AttackCooldown => (constants::PLAYER_MIN_COOLDOWN, f32::MAX),
+            Stat::ProjectilePassthrough | Stat::OrbitalMaxLaps => (1., f32::MAX),
+            Stat::OrbRadius => (constants::PLAYER_SIZE, f32::MAX),
+            _ => (0., f32::MAX),
+        }
+    }
+
     pub fn get_random_range(&self) -> Vec<PowerUpValue> {
         match self {
             Stat::MaxHealth => {
@@ -252,6 +670,16 @@ impl Stat {
             Stat::MoveSpeed => {
                 vec![PowerUpValue::Percent(5), PowerUpValue::Percent(10)]
             }
+            Stat::DashCharges => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::DashCooldown => {
+                vec![
+                    PowerUpValue::Percent(-5),
+                    PowerUpValue::Percent(-10),
+                    PowerUpValue::Percent(-20),
+                ]
+            }
             Stat::AttachDamage => {
                 vec![
                     PowerUpValue::Percent(5),
@@ -299,12 +727,147 @@ impl Stat {
                     PowerUpValue::Percent(40),
                 ]
             }
+            Stat::OrbRadius => {
+                vec![PowerUpValue::Percent(10), PowerUpValue::Percent(20)]
+            }
+            Stat::OrbSpeed => {
+                vec![
+                    PowerUpValue::Percent(10),
+                    PowerUpValue::Percent(20),
+                    PowerUpValue::Percent(30),
+                ]
+            }
+            Stat::OrbRings => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::OrbReverse => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::OrbitalProjectileAmount => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::OrbitalMaxLaps => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::LaserBeam => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::DroneCount => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::TurretCount => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::Luck => {
+                vec![
+                    PowerUpValue::Percent(10),
+                    PowerUpValue::Percent(20),
+                    PowerUpValue::Percent(30),
+                ]
+            }
+            Stat::Reroll => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::Skip => {
+                vec![PowerUpValue::Amount(1)]
+            }
+            Stat::Banish => {
+                vec![PowerUpValue::Amount(1)]
+            }
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn power_up_offers_are_unique_and_luck_improves_rarity() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut stats = PlayerStats::default();
+        for _ in 0..100 {
+            let offers = PowerUp::new_random_offers(&stats, 3, &mut rng);
+            assert_eq!(offers.len(), 3);
+            for (i, offer) in offers.iter().enumerate() {
+                assert!(offers[i + 1..].iter().all(|other| other.stat != offer.stat));
+            }
+        }
+
+        let count_rare = |stats: &PlayerStats, rng: &mut StdRng| {
+            (0..1000)
+                .filter(|_| {
+                    PowerUp::new_random(Stat::Armor, stats.luck, rng).rarity != Rarity::Common
+                })
+                .count()
+        };
+        // banished stats are never offered
+        assert!(stats.banish(Stat::Armor));
+        assert!(!stats.banish(Stat::Recovery));
+        for _ in 0..100 {
+            let offers = PowerUp::new_random_offers(&stats, 3, &mut rng);
+            assert!(offers.iter().all(|offer| offer.stat != Stat::Armor));
+        }
+
+        let unlucky = count_rare(&stats, &mut rng);
+        stats.add_modifier(
+            Stat::Luck,
+            PowerUpValue::Percent(200),
+            ModifierSource::PowerUp,
+        );
+        assert_eq!(stats.luck, 300.);
+        let lucky = count_rare(&stats, &mut rng);
+        assert!(lucky > unlucky);
+
+        // rarer power ups are stronger
+        assert_eq!(
+            PowerUpValue::Percent(10).scaled(Rarity::Legendary.multiplier()),
+            PowerUpValue::Percent(30)
+        );
+        assert_eq!(
+            PowerUpValue::Amount(1).scaled(Rarity::Rare.multiplier()),
+            PowerUpValue::Amount(2)
+        );
+        assert_eq!(
+            PowerUpValue::Percent(-20).scaled(Rarity::Legendary.multiplier()),
+            PowerUpValue::Percent(constants::POWER_UP_MIN_PERCENT)
+        );
+        assert_eq!(
+            PowerUpValue::Percent(-5).scaled(Rarity::Rare.multiplier()),
+            PowerUpValue::Percent(-8)
+        );
+        assert_eq!(
+            PowerUpValue::Amount(1),
+            PowerUp::new_random(Stat::AttackAmount, 10_000., &mut rng).value
+        );
+    }
+
+    #[test]
+    fn orb_reverse_flips_ring_directions() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut stats = PlayerStats::default();
+        assert_eq!(stats.orb_direction, OrbDirection::CounterClockwise);
+
+        // each orb reversal flips the direction, whatever its rarity
+        let reverse = PowerUp {
+            stat: Stat::OrbReverse,
+            value: PowerUpValue::Amount(1),
+            rarity: Rarity::Legendary,
+        };
+        stats.add_power_up(&reverse);
+        assert_eq!(stats.orb_direction, OrbDirection::Clockwise);
+        assert_eq!(
+            stats.get_orb_ring_direction(1),
+            OrbDirection::CounterClockwise
+        );
+        stats.add_power_up(&reverse);
+        assert_eq!(stats.orb_direction, OrbDirection::CounterClockwise);
 
-    pub fn new_random() -> Self {
-        let between = Uniform::from(0..Self::ALL.len());
-        let mut rng = rand::thread_rng();
-        Self::ALL[rng.sample(between)]
+        // so it is never offered as a rarer power up
+        let offer = PowerUp::new_random(Stat::OrbReverse, 10_000., &mut rng);
+        assert_eq!(offer.value, PowerUpValue::Amount(1));
+        assert_eq!(offer.rarity, Rarity::Common);
     }
 }
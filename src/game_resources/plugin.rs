@@ -6,23 +6,61 @@ pub struct GameResourcesPlugin;
 
 impl Plugin for GameResourcesPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PlayerStats>()
+        app.init_resource::<SelectedCharacters>()
+            .init_resource::<PlayerCount>()
             .init_resource::<EnemyStats>()
             .init_resource::<PlayerScore>()
             .init_resource::<GameTimer>()
-            .add_systems(OnEnter(AppState::Game), reset_game_resources)
+            .init_resource::<XpCurve>()
+            .init_resource::<MetaProgress>()
+            .init_resource::<Leaderboard>()
+            .init_resource::<GameRng>()
+            .init_resource::<ChosenSeed>()
+            .add_systems(Startup, (load_meta_progress, load_leaderboard))
+            .add_systems(
+                OnEnter(AppState::Game),
+                (reset_game_resources, reset_game_rng),
+            )
+            .add_systems(OnEnter(AppState::GameOver), award_run_gold)
             .add_systems(Update, tick_game_timer.run_if(in_game_not_paused));
     }
 }
 
 fn reset_game_resources(mut commands: Commands) {
-    commands.insert_resource(PlayerStats::default());
     commands.insert_resource(EnemyStats::default());
     commands.insert_resource(PlayerScore::default());
     commands.insert_resource(GameTimer::default());
     commands.insert_resource(EnemyStatsTimer::default());
 }
 
+/// System that seeds the random numbers of a new run, with the seed chosen on the main menu if
+/// there is one
+pub fn reset_game_rng(mut rng: ResMut<GameRng>, chosen_seed: Res<ChosenSeed>) {
+    *rng = GameRng::new(chosen_seed.0.unwrap_or_else(rand::random));
+}
+
+fn load_meta_progress(mut commands: Commands, storage: Res<GameStorage>) {
+    if let Some(progress) = storage.load_json::<MetaProgress>(MetaProgress::STORAGE_KEY) {
+        commands.insert_resource(progress);
+    }
+}
+
+fn load_leaderboard(mut commands: Commands, storage: Res<GameStorage>) {
+    if let Some(leaderboard) = storage.load_json::<Leaderboard>(Leaderboard::STORAGE_KEY) {
+        commands.insert_resource(leaderboard);
+    }
+}
+
+fn award_run_gold(
+    score: Res<PlayerScore>,
+    timer: Res<GameTimer>,
+    storage: Res<GameStorage>,
+    mut progress: ResMut<MetaProgress>,
+) {
+    progress.gold += gold_for_run(&score, &timer);
+    storage.save_json(MetaProgress::STORAGE_KEY, &*progress);
+}
+
 fn tick_game_timer(time: Res<Time>, mut game_timer: ResMut<GameTimer>) {
     game_timer.0.tick(time.delta());
 }
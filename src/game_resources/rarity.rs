@@ -0,0 +1,79 @@
+use std::fmt;
+
+use bevy::{color::palettes::css, prelude::*};
+use rand::Rng;
+
+/// How rare a power up is. Rarer power ups are offered less often, but have stronger values.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    pub const ALL: [Self; 4] = [Self::Common, Self::Rare, Self::Epic, Self::Legendary];
+
+    /// Returns how likely the rarity is to be rolled, relative to the other rarities. Luck is a
+    /// percentage that scales the weight of everything rarer than common.
+    pub fn weight(&self, luck: f32) -> f32 {
+        let base = match self {
+            Rarity::Common => 60.,
+            Rarity::Rare => 25.,
+            Rarity::Epic => 11.,
+            Rarity::Legendary => 4.,
+        };
+        match self {
+            Rarity::Common => base,
+            _ => base * luck.max(0.) / 100.,
+        }
+    }
+
+    /// Returns how much the value of a power up is multiplied by
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            Rarity::Common => 1.,
+            Rarity::Rare => 1.5,
+            Rarity::Epic => 2.,
+            Rarity::Legendary => 3.,
+        }
+    }
+
+    /// Color of the power up card
+    pub fn color(&self) -> Srgba {
+        match self {
+            Rarity::Common => css::SLATE_GRAY,
+            Rarity::Rare => css::ROYAL_BLUE,
+            Rarity::Epic => css::DARK_ORCHID,
+            Rarity::Legendary => css::DARK_GOLDENROD,
+        }
+    }
+
+    /// Picks a random rarity using the weights for the given luck
+    pub fn new_random(luck: f32, rng: &mut impl Rng) -> Self {
+        let total: f32 = Self::ALL.iter().map(|rarity| rarity.weight(luck)).sum();
+        let mut roll = rng.gen_range(0. ..total);
+        for rarity in Self::ALL {
+            let weight = rarity.weight(luck);
+            if roll < weight {
+                return rarity;
+            }
+            roll -= weight;
+        }
+        Rarity::Common
+    }
+}
+
+impl fmt::Display for Rarity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rarity::Common => write!(f, "Common"),
+            Rarity::Rare => write!(f, "Rare"),
+            Rarity::Epic => write!(f, "Epic"),
+            Rarity::Legendary => write!(f, "Legendary"),
+        }
+    }
+}
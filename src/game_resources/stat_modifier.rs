@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::{PowerUpValue, Stat};
+
+/// Where a stat modifier came from.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum ModifierSource {
+    /// The character's starting stats
+    Character,
+    /// The character's bonus gained every level
+    Passive,
+    /// A power up chosen on level up
+    PowerUp,
+    /// An upgrade bought in the shop between runs
+    Meta,
+}
+
+/// How a modifier changes a stat.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum ModifierValue {
+    /// Added to the base value
+    Add(f32),
+    /// Percentage of the base value plus every [`ModifierValue::Add`]. Percentages are summed
+    /// rather than compounded, so `+10%` twice is `+20%`.
+    Percent(f32),
+}
+
+impl From<PowerUpValue> for ModifierValue {
+    fn from(value: PowerUpValue) -> Self {
+        match value {
+            PowerUpValue::Percent(val) => ModifierValue::Percent(val as f32),
+            PowerUpValue::Amount(val) => ModifierValue::Add(val as f32),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub struct StatModifier {
+    pub stat: Stat,
+    pub value: ModifierValue,
+    pub source: ModifierSource,
+}
+
+/// Every modifier applied to a player's stats.
+#[derive(Default, Debug, Clone, Reflect)]
+#[reflect(Default, Debug)]
+pub struct StatModifiers(pub Vec<StatModifier>);
+
+impl StatModifiers {
+    pub fn push(&mut self, stat: Stat, value: impl Into<ModifierValue>, source: ModifierSource) {
+        self.0.push(StatModifier {
+            stat,
+            value: value.into(),
+            source,
+        });
+    }
+
+    /// Returns the value of a stat: its base value plus every added amount, scaled by the sum of
+    /// every percentage, and kept within the stat's limits
+    pub fn get_value(&self, stat: Stat) -> f32 {
+        let mut add = 0.;
+        let mut percent = 0.;
+        for modifier in self.0.iter().filter(|modifier| modifier.stat == stat) {
+            match modifier.value {
+                ModifierValue::Add(val) => add += val,
+                ModifierValue::Percent(val) => percent += val,
+            }
+        }
+        let (min, max) = stat.limits();
+        ((stat.base_value() + add) * (1. + percent / 100.)).clamp(min, max)
+    }
+
+    /// Returns the value of a stat that counts something
+    pub fn get_count(&self, stat: Stat) -> u32 {
+        self.get_value(stat).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_sum_percentages_and_respect_limits() {
+        let mut modifiers = StatModifiers::default();
+        let base = Stat::AttachDamage.base_value();
+        assert_eq!(modifiers.get_value(Stat::AttachDamage), base);
+
+        modifiers.push(
+            Stat::AttachDamage,
+            ModifierValue::Add(10.),
+            ModifierSource::Character,
+        );
+        modifiers.push(
+            Stat::AttachDamage,
+            PowerUpValue::Percent(10),
+            ModifierSource::PowerUp,
+        );
+        modifiers.push(
+            Stat::AttachDamage,
+            PowerUpValue::Percent(10),
+            ModifierSource::PowerUp,
+        );
+        assert_eq!(modifiers.get_value(Stat::AttachDamage), (base + 10.) * 1.2);
+
+        // cooldowns can't drift to zero
+        for _ in 0..20 {
+            modifiers.push(
+                Stat::AttackCooldown,
+                PowerUpValue::Percent(-20),
+                ModifierSource::PowerUp,
+            );
+        }
+        assert_eq!(
+            modifiers.get_value(Stat::AttackCooldown),
+            Stat::AttackCooldown.limits().0
+        );
+
+        // other stats are unaffected
+        assert_eq!(modifiers.get_count(Stat::DroneCount), 0);
+    }
+}
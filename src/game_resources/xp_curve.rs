@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+/// How many points each level takes to reach the next one.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub enum XpCurve {
+    /// `base * level ^ exponent`
+    Polynomial { base: f32, exponent: f32 },
+    /// `base * growth ^ (level - 1)`
+    Exponential { base: f32, growth: f32 },
+    /// Points for each level, starting from level 1. Levels past the end of the table keep
+    /// increasing by the difference between the last two entries.
+    Table(Vec<u32>),
+}
+
+impl Default for XpCurve {
+    fn default() -> Self {
+        XpCurve::Polynomial {
+            base: 10.,
+            exponent: 1.5,
+        }
+    }
+}
+
+impl XpCurve {
+    /// Returns the points needed to go from `level` to the next level
+    pub fn points_for_level(&self, level: u32) -> u32 {
+        let level = level.max(1);
+        let points = match self {
+            XpCurve::Polynomial { base, exponent } => {
+                (base * (level as f32).powf(*exponent)).round() as u32
+            }
+            XpCurve::Exponential { base, growth } => {
+                (base * growth.powi(level as i32 - 1)).round() as u32
+            }
+            XpCurve::Table(table) => {
+                let index = level as usize - 1;
+                match (table.get(index), table.last()) {
+                    (Some(points), _) => *points,
+                    (None, Some(last)) => {
+                        let step = match table.len() {
+                            0 | 1 => 0,
+                            len => last.saturating_sub(table[len - 2]),
+                        };
+                        last + step * (index + 1 - table.len()) as u32
+                    }
+                    (None, None) => 0,
+                }
+            }
+        };
+        // every level takes at least one point, so level ups always end
+        points.max(1)
+    }
+}
@@ -4,6 +4,12 @@ use bevy::prelude::*;
 pub enum AppState {
     #[default]
     Menu,
+    CharacterSelect,
+    Shop,
+    Achievements,
+    Leaderboard,
+    Settings,
+    Seed,
     Game,
     GameOver,
 }
@@ -13,6 +19,8 @@ pub enum GameState {
     #[default]
     Play,
     Paused,
+    /// The settings screen, opened from the pause menu
+    Settings,
     PowerUp,
 }
 
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use bevy::prelude::*;
 
 use crate::*;
@@ -7,6 +9,7 @@ pub struct HealthPlugin;
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<DeathEvent>()
+            .add_event::<DamageEvent>()
             .add_systems(Update, handle_health_changed.run_if(in_game));
     }
 }
@@ -15,11 +18,50 @@ impl Plugin for HealthPlugin {
 #[reflect(Debug)]
 pub struct DeathEvent(pub Entity);
 
-#[derive(Component, Debug, Reflect)]
+/// Sent when a weapon damages an entity, or an enemy damages a player
+#[derive(Event, Debug, Reflect)]
+#[reflect(Debug)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub source: DamageSource,
+}
+
+/// What dealt damage to an entity
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum DamageSource {
+    Projectile,
+    /// An orb or ring of fire, and the player it belongs to
+    Orb(Entity),
+    /// A laser beam, and the player firing it
+    Laser(Entity),
+    Shockwave,
+    Explosion,
+    /// An enemy attacking a player
+    Enemy,
+}
+
+impl fmt::Display for DamageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DamageSource::Projectile => write!(f, "Projectile"),
+            DamageSource::Orb(_) => write!(f, "Orb"),
+            DamageSource::Laser(_) => write!(f, "Laser"),
+            DamageSource::Shockwave => write!(f, "Shockwave"),
+            DamageSource::Explosion => write!(f, "Explosion"),
+            DamageSource::Enemy => write!(f, "Enemy"),
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Default, Debug)]
 pub struct Health {
     pub current: f32,
     pub max_health: f32,
+    /// What dealt the most recent damage
+    pub last_damage_source: Option<DamageSource>,
 }
 
 impl Health {
@@ -27,16 +69,24 @@ impl Health {
         Self {
             current: health,
             max_health: health,
+            last_damage_source: None,
         }
     }
+
+    /// Removes health, remembering what dealt the damage
+    pub fn damage(&mut self, amount: f32, source: DamageSource) {
+        self.current -= amount;
+        self.last_damage_source = Some(source);
+    }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max_health);
+    }
 }
 
 impl Default for Health {
     fn default() -> Self {
-        Self {
-            current: 100.,
-            max_health: 100.,
-        }
+        Self::new(100.)
     }
 }
 
@@ -20,15 +20,49 @@ impl Plugin for HudUIPlugin {
                     )
                         .run_if(resource_exists_and_changed::<PlayerScore>),
                     update_timer_label,
+                    update_orbital_laps_label,
+                    update_dash_bar,
+                    update_items_label,
+                    update_ultimate_bar.run_if(resource_exists_and_changed::<UltimateMeter>),
+                    show_achievement_toasts.run_if(on_event::<AchievementUnlocked>()),
                 )
                     .run_if(in_game),
             );
     }
 }
 
+/// Column that achievement toasts are added to
 #[derive(Component, Default, Debug, Reflect)]
 #[reflect(Component, Default, Debug)]
-struct HealthBar;
+struct AchievementToasts;
+
+/// Health bar of the player with the given index
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct HealthBar(u32);
+
+/// Dash bar of the player with the given index
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct DashBar(u32);
+
+/// Dash charges of the player with the given index
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct DashChargesLabel(u32);
+
+/// Items held by the player with the given index
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct ItemsLabel(u32);
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct UltimateBar;
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct UltimateLabel;
 
 #[derive(Component, Default, Debug, Reflect)]
 #[reflect(Component, Default, Debug)]
@@ -50,7 +84,27 @@ struct KillCountLabel;
 #[reflect(Component, Default, Debug)]
 struct TotalPointsLabel;
 
-fn setup_hud(mut commands: Commands, score: Res<PlayerScore>) {
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct OrbitalLapsLabel;
+
+fn setup_hud(
+    mut commands: Commands,
+    score: Res<PlayerScore>,
+    player_count: Res<PlayerCount>,
+    selected_characters: Res<SelectedCharacters>,
+    meta_progress: Res<MetaProgress>,
+) {
+    // the players are spawned in the same frame, so start from each character's stats
+    let player_stats: Vec<PlayerStats> = (0..player_count.0)
+        .map(|index| meta_progress.starting_stats(selected_characters.get(index)))
+        .collect();
+    let orbital_max_laps = player_stats
+        .iter()
+        .map(|stats| stats.orbital_max_laps)
+        .max()
+        .unwrap_or_default();
+
     root(
         &mut commands,
         (Name::new("HUD layout"), StateScoped(AppState::Game)),
@@ -59,19 +113,33 @@ fn setup_hud(mut commands: Commands, score: Res<PlayerScore>) {
     .with_children(|p| {
         hud_level_progress(p, &score);
         hud_second_row_layout(p, |p| {
-            // left column, contains score
+            // left column, contains the health and dash bars of every player, and the shared
+            // ultimate meter
             node(p, (), c_col).with_children(|p| {
-                hud_health_bar(p);
+                for (index, stats) in player_stats.iter().enumerate() {
+                    hud_health_bar(p, index as u32);
+                    hud_dash_bar(p, index as u32, stats);
+                    hud_items_label(p, index as u32, stats);
+                }
+                hud_ultimate_bar(p);
             });
-            // middle column, contains timer
+            // middle column, contains timer and achievement toasts
             node(p, (), (c_col, c_center)).with_children(|p| {
                 hud_timer_label(p);
+                node(p, AchievementToasts, (c_col, c_center));
             });
             // right column, contains enemies killed and score
             node(p, (), c_col).with_children(|p| {
                 hud_last_column_layout(p, |p| {
                     hud_label_value_column(p, "KILL COUNT", "0", css::RED, KillCountLabel);
                     hud_label_value_column(p, "SCORE", "0", css::GOLD, TotalPointsLabel);
+                    hud_label_value_column(
+                        p,
+                        "ORBITS",
+                        format_orbital_laps(0, orbital_max_laps),
+                        css::LIGHT_CORAL,
+                        OrbitalLapsLabel,
+                    );
                 });
             });
         });
@@ -102,8 +170,7 @@ fn hud_last_column_layout(parent: &mut ChildBuilder, children: impl FnOnce(&mut
         (),
         (c_row, c_w_100, |b: &mut NodeBundle| {
             b.style.display = Display::Grid;
-            b.style.grid_template_columns =
-                vec![RepeatedGridTrack::fr(1, 1.), RepeatedGridTrack::fr(1, 1.)];
+            b.style.grid_template_columns = RepeatedGridTrack::fr(3, 1.);
             b.style.grid_template_rows = RepeatedGridTrack::min_content(1);
             b.style.justify_content = JustifyContent::SpaceBetween;
         }),
@@ -144,8 +211,9 @@ fn hud_label_value_column(
     });
 }
 
-fn hud_health_bar(parent: &mut ChildBuilder) {
-    node(parent, (), (c_col, c_width(200.))).with_children(|p| {
+fn hud_health_bar(parent: &mut ChildBuilder, index: u32) {
+    let margin_top = if index > 0 { 10. } else { 0. };
+    node(parent, (), (c_col, c_width(200.), c_margin_top(margin_top))).with_children(|p| {
         progress_bar_widget(
             p,
             ProgressBar {
@@ -154,11 +222,77 @@ fn hud_health_bar(parent: &mut ChildBuilder) {
                 height: Val::Px(20.),
                 color: css::LIMEGREEN.into(),
             },
-            HealthBar,
+            HealthBar(index),
         );
     });
 }
 
+fn hud_dash_bar(parent: &mut ChildBuilder, index: u32, stats: &PlayerStats) {
+    node(
+        parent,
+        (),
+        (c_row, c_width(200.), c_margin_top(5.), c_align_center),
+    )
+    .with_children(|p| {
+        node(p, (), (c_col, c_flex_grow)).with_children(|p| {
+            progress_bar_widget(
+                p,
+                ProgressBar {
+                    amount: 1.,
+                    total: 1.,
+                    height: Val::Px(8.),
+                    color: css::DEEP_SKY_BLUE.into(),
+                },
+                DashBar(index),
+            );
+        });
+        node(p, (), c_padding_horizontal(5.)).with_children(|p| {
+            hud_label_value_text(
+                p,
+                format_dash_charges(stats.dash_charges),
+                css::DEEP_SKY_BLUE,
+                DashChargesLabel(index),
+            );
+        });
+    });
+}
+
+fn hud_items_label(parent: &mut ChildBuilder, index: u32, stats: &PlayerStats) {
+    node(parent, (), (c_row, c_width(200.), c_margin_top(5.))).with_children(|p| {
+        hud_label_value_text(
+            p,
+            format_items(&stats.items),
+            css::TURQUOISE,
+            ItemsLabel(index),
+        );
+    });
+}
+
+fn hud_ultimate_bar(parent: &mut ChildBuilder) {
+    node(
+        parent,
+        (),
+        (c_row, c_width(200.), c_margin_top(10.), c_align_center),
+    )
+    .with_children(|p| {
+        node(p, (), (c_col, c_flex_grow)).with_children(|p| {
+            progress_bar_widget(
+                p,
+                ProgressBar {
+                    amount: 0.,
+                    total: 1.,
+                    height: Val::Px(12.),
+                    color: css::GOLD.into(),
+                },
+                UltimateBar,
+            );
+        });
+        node(p, (), c_padding_horizontal(5.)).with_children(|p| {
+            hud_label_value_text(p, format_ultimate(false), css::GOLD, UltimateLabel);
+        });
+    });
+}
+
 fn hud_timer_label(parent: &mut ChildBuilder) {
     text(
         parent,
@@ -171,6 +305,39 @@ fn hud_timer_label(parent: &mut ChildBuilder) {
     );
 }
 
+fn hud_achievement_toast(parent: &mut ChildBuilder, achievement: Achievement) {
+    node(
+        parent,
+        (
+            Name::new("Achievement Toast"),
+            Lifetime::from_seconds(constants::ACHIEVEMENT_TOAST_SECS),
+        ),
+        (
+            c_col,
+            c_center,
+            c_border_white,
+            c_background_color(css::DARK_SLATE_GRAY),
+            c_padding(10.),
+            c_margin_bottom(10.),
+            c_border_radius(10.),
+        ),
+    )
+    .with_children(|p| {
+        text(
+            p,
+            format!("Achievement unlocked: {}", achievement),
+            (),
+            TextStyle {
+                color: css::GOLD.into(),
+                ..default()
+            },
+        );
+        if let Some(reward) = achievement.reward() {
+            text(p, format!("{}", reward), (), TextStyle::default());
+        }
+    });
+}
+
 fn hud_level_progress(parent: &mut ChildBuilder, score: &PlayerScore) {
     node(
         parent,
@@ -206,17 +373,69 @@ fn hud_level_progress(parent: &mut ChildBuilder, score: &PlayerScore) {
 }
 
 fn update_score_progress_bar(
-    health_query: Query<&Health, (With<Player>, Changed<Health>)>,
-    mut progress_bar_query: Query<&mut ProgressBar, With<HealthBar>>,
+    health_query: Query<(&Player, &Health), Changed<Health>>,
+    mut progress_bar_query: Query<(&mut ProgressBar, &HealthBar)>,
 ) {
-    for health in health_query.iter() {
-        for mut progress_bar in progress_bar_query.iter_mut() {
+    for (player, health) in health_query.iter() {
+        for (mut progress_bar, bar) in progress_bar_query.iter_mut() {
+            if bar.0 != player.index {
+                continue;
+            }
             progress_bar.amount = health.current;
             progress_bar.total = health.max_health;
         }
     }
 }
 
+/// Shows how far the next dash has recharged, and how many dashes are ready
+fn update_dash_bar(
+    dash_query: Query<(&Player, &Dash), Changed<Dash>>,
+    mut bar_query: Query<(&mut ProgressBar, &DashBar)>,
+    mut label_query: Query<(&mut Text, &DashChargesLabel)>,
+) {
+    for (player, dash) in dash_query.iter() {
+        for (mut progress_bar, bar) in bar_query.iter_mut() {
+            if bar.0 == player.index {
+                progress_bar.amount = dash.recharge_fraction();
+                progress_bar.total = 1.;
+            }
+        }
+        for (mut text, label) in label_query.iter_mut() {
+            if label.0 == player.index {
+                text.sections[0].value = format_dash_charges(dash.charges);
+            }
+        }
+    }
+}
+
+fn update_items_label(
+    stats_query: Query<(&Player, &PlayerStats), Changed<PlayerStats>>,
+    mut label_query: Query<(&mut Text, &ItemsLabel)>,
+) {
+    for (player, stats) in stats_query.iter() {
+        for (mut text, label) in label_query.iter_mut() {
+            if label.0 == player.index {
+                text.sections[0].value = format_items(&stats.items);
+            }
+        }
+    }
+}
+
+/// Shows how full the ultimate meter is, and whether it is ready to use
+fn update_ultimate_bar(
+    meter: Res<UltimateMeter>,
+    mut bar_query: Query<&mut ProgressBar, With<UltimateBar>>,
+    mut label_query: Query<&mut Text, With<UltimateLabel>>,
+) {
+    for mut progress_bar in bar_query.iter_mut() {
+        progress_bar.amount = meter.fraction();
+        progress_bar.total = 1.;
+    }
+    for mut text in label_query.iter_mut() {
+        text.sections[0].value = format_ultimate(meter.is_full());
+    }
+}
+
 fn update_level_up_bar(
     score: Res<PlayerScore>,
     mut bar_query: Query<&mut ProgressBar, With<LevelUpBar>>,
@@ -259,3 +478,58 @@ fn update_timer_label(timer: Res<GameTimer>, mut label_query: Query<&mut Text, W
         text.sections[0].value = timer.to_time_string();
     }
 }
+
+fn show_achievement_toasts(
+    mut commands: Commands,
+    mut events: EventReader<AchievementUnlocked>,
+    container_query: Query<Entity, With<AchievementToasts>>,
+) {
+    for container in container_query.iter() {
+        commands.entity(container).with_children(|p| {
+            for event in events.read() {
+                hud_achievement_toast(p, event.0);
+            }
+        });
+    }
+}
+
+/// Shows the most laps completed by any orbital projectile in flight, out of the maximum.
+fn update_orbital_laps_label(
+    stats_query: Query<&PlayerStats>,
+    orbital_query: Query<&OrbitalProjectile>,
+    mut label_query: Query<&mut Text, With<OrbitalLapsLabel>>,
+) {
+    let laps = orbital_query.iter().map(|o| o.laps).max().unwrap_or(0);
+    let max_laps = stats_query
+        .iter()
+        .map(|stats| stats.orbital_max_laps)
+        .max()
+        .unwrap_or(0);
+    for mut text in label_query.iter_mut() {
+        text.sections[0].value = format_orbital_laps(laps, max_laps);
+    }
+}
+
+fn format_orbital_laps(laps: u32, max_laps: u32) -> String {
+    format!("{}/{}", laps, max_laps)
+}
+
+fn format_items(items: &[Item]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string().to_uppercase())
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+fn format_dash_charges(charges: u32) -> String {
+    format!("DASH x{}", charges)
+}
+
+fn format_ultimate(ready: bool) -> String {
+    if ready {
+        "ULT READY".to_string()
+    } else {
+        "ULT".to_string()
+    }
+}
@@ -0,0 +1,810 @@
+use std::fmt;
+
+use bevy::{
+    input::{gamepad::GamepadConnectionEvent, InputSystem},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::GameStorage;
+
+pub struct InputActionsPlugin;
+
+impl Plugin for InputActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .init_resource::<Rebinding>()
+            .init_resource::<ConnectedGamepads>()
+            .register_type::<InputBindings>()
+            .add_systems(Startup, load_input_bindings)
+            .add_systems(
+                PreUpdate,
+                (
+                    update_connected_gamepads.run_if(on_event::<GamepadConnectionEvent>()),
+                    capture_rebinding.run_if(is_rebinding),
+                    (update_action_state, update_player_input),
+                )
+                    .chain()
+                    .after(InputSystem),
+            );
+    }
+}
+
+/// How far an analog input must be pushed before the action counts as pressed
+const PRESS_THRESHOLD: f32 = 0.5;
+
+/// Actions the player can perform, independent of the device used to perform them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Dash,
+    Ultimate,
+    Pause,
+    Confirm,
+}
+
+impl InputAction {
+    pub const ALL: [Self; 8] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Dash,
+        Self::Ultimate,
+        Self::Pause,
+        Self::Confirm,
+    ];
+}
+
+impl fmt::Display for InputAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputAction::MoveUp => write!(f, "Move Up"),
+            InputAction::MoveDown => write!(f, "Move Down"),
+            InputAction::MoveLeft => write!(f, "Move Left"),
+            InputAction::MoveRight => write!(f, "Move Right"),
+            InputAction::Dash => write!(f, "Dash"),
+            InputAction::Ultimate => write!(f, "Ultimate"),
+            InputAction::Pause => write!(f, "Pause"),
+            InputAction::Confirm => write!(f, "Confirm"),
+        }
+    }
+}
+
+/// A physical input that can trigger an [`InputAction`].
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+pub enum InputBinding {
+    Key(KeyCode),
+    /// A button on any connected gamepad
+    Button(GamepadButtonType),
+    /// One direction of a stick on any connected gamepad
+    Stick {
+        axis: GamepadAxisType,
+        positive: bool,
+    },
+}
+
+impl InputBinding {
+    /// Returns how far the input is pressed, from `0` to `1`
+    pub fn value(
+        &self,
+        keys: &ButtonInput<KeyCode>,
+        gamepads: &Gamepads,
+        buttons: &ButtonInput<GamepadButton>,
+        axes: &Axis<GamepadAxis>,
+        gamepad_filter: GamepadFilter,
+    ) -> f32 {
+        let mut gamepads = gamepads.iter().filter(|gamepad| match gamepad_filter {
+            GamepadFilter::Any => true,
+            GamepadFilter::Only(only) => *gamepad == only,
+            GamepadFilter::Disabled => false,
+        });
+        match *self {
+            InputBinding::Key(key) => {
+                if keys.pressed(key) {
+                    1.
+                } else {
+                    0.
+                }
+            }
+            InputBinding::Button(button_type) => {
+                let pressed = gamepads
+                    .any(|gamepad| buttons.pressed(GamepadButton::new(gamepad, button_type)));
+                if pressed {
+                    1.
+                } else {
+                    0.
+                }
+            }
+            InputBinding::Stick { axis, positive } => gamepads
+                .filter_map(|gamepad| axes.get(GamepadAxis::new(gamepad, axis)))
+                .map(|value| if positive { value } else { -value })
+                .fold(0., f32::max)
+                .clamp(0., 1.),
+        }
+    }
+
+    /// Returns `true` if both inputs are keys, both are gamepad buttons, or both are sticks
+    pub fn is_same_kind(&self, other: &InputBinding) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputBinding::Key(key) => {
+                let name = format!("{:?}", key);
+                let name = ["Key", "Digit", "Arrow"]
+                    .iter()
+                    .find_map(|prefix| name.strip_prefix(prefix))
+                    .unwrap_or(&name);
+                write!(f, "{}", name)
+            }
+            InputBinding::Button(button) => write!(f, "{:?}", button),
+            InputBinding::Stick { axis, positive } => {
+                write!(f, "{:?}{}", axis, if *positive { "+" } else { "-" })
+            }
+        }
+    }
+}
+
+/// The inputs bound to each [`InputAction`]. They can be re-bound on the settings screen, and are
+/// saved to [`GameStorage`] under [`InputBindings::STORAGE_KEY`].
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct InputBindings {
+    pub bindings: HashMap<InputAction, Vec<InputBinding>>,
+    /// The gamepads inputs are read from
+    pub gamepad: GamepadFilter,
+}
+
+/// Which gamepads an [`InputBindings`] reads inputs from
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum GamepadFilter {
+    #[default]
+    Any,
+    Only(Gamepad),
+    /// No gamepad, for a co-op player without a gamepad of their own
+    Disabled,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let bindings = HashMap::from_iter([
+            (
+                InputAction::MoveUp,
+                vec![
+                    InputBinding::Key(KeyCode::ArrowUp),
+                    InputBinding::Key(KeyCode::KeyW),
+                    InputBinding::Button(GamepadButtonType::DPadUp),
+                    InputBinding::Stick {
+                        axis: GamepadAxisType::LeftStickY,
+                        positive: true,
+                    },
+                ],
+            ),
+            (
+                InputAction::MoveDown,
+                vec![
+                    InputBinding::Key(KeyCode::ArrowDown),
+                    InputBinding::Key(KeyCode::KeyS),
+                    InputBinding::Button(GamepadButtonType::DPadDown),
+                    InputBinding::Stick {
+                        axis: GamepadAxisType::LeftStickY,
+                        positive: false,
+                    },
+                ],
+            ),
+            (
+                InputAction::MoveLeft,
+                vec![
+                    InputBinding::Key(KeyCode::ArrowLeft),
+                    InputBinding::Key(KeyCode::KeyA),
+                    InputBinding::Button(GamepadButtonType::DPadLeft),
+                    InputBinding::Stick {
+                        axis: GamepadAxisType::LeftStickX,
+                        positive: false,
+                    },
+                ],
+            ),
+            (
+                InputAction::MoveRight,
+                vec![
+                    InputBinding::Key(KeyCode::ArrowRight),
+                    InputBinding::Key(KeyCode::KeyD),
+                    InputBinding::Button(GamepadButtonType::DPadRight),
+                    InputBinding::Stick {
+                        axis: GamepadAxisType::LeftStickX,
+                        positive: true,
+                    },
+                ],
+            ),
+            (
+                InputAction::Dash,
+                vec![
+                    InputBinding::Key(KeyCode::ShiftLeft),
+                    InputBinding::Key(KeyCode::ShiftRight),
+                    InputBinding::Button(GamepadButtonType::East),
+                    InputBinding::Button(GamepadButtonType::RightTrigger),
+                ],
+            ),
+            (
+                InputAction::Ultimate,
+                vec![
+                    InputBinding::Key(KeyCode::KeyE),
+                    InputBinding::Key(KeyCode::ControlRight),
+                    InputBinding::Button(GamepadButtonType::North),
+                    InputBinding::Button(GamepadButtonType::LeftTrigger),
+                ],
+            ),
+            (
+                InputAction::Pause,
+                vec![
+                    InputBinding::Key(KeyCode::Escape),
+                    InputBinding::Button(GamepadButtonType::Start),
+                ],
+            ),
+            (
+                InputAction::Confirm,
+                vec![
+                    InputBinding::Key(KeyCode::Enter),
+                    InputBinding::Key(KeyCode::Space),
+                    InputBinding::Button(GamepadButtonType::South),
+                ],
+            ),
+        ]);
+        Self {
+            bindings,
+            gamepad: GamepadFilter::Any,
+        }
+    }
+}
+
+impl InputBindings {
+    pub const STORAGE_KEY: &'static str = "bindings";
+
+    /// Returns the bindings for a player. A single player can use any input, but in local co-op the
+    /// first player uses the keys bound on the settings screen, except the arrow keys, and the
+    /// second uses the arrow keys. Each uses the gamepad at their index in `gamepads`, if there is
+    /// one.
+    pub fn for_player(&self, index: u32, player_count: u32, gamepads: &ConnectedGamepads) -> Self {
+        let mut bindings = self.clone();
+        if player_count <= 1 {
+            return bindings;
+        }
+
+        let defaults = Self::default();
+        for (action, inputs) in bindings.bindings.iter_mut() {
+            if index == 0 {
+                inputs.retain(|binding| match binding {
+                    InputBinding::Key(key) => !PLAYER_TWO_KEYS.contains(key),
+                    _ => true,
+                });
+            } else {
+                inputs.retain(|binding| !matches!(binding, InputBinding::Key(_)));
+                inputs.extend(defaults.get(*action).iter().filter(|binding| {
+                    matches!(binding, InputBinding::Key(key) if PLAYER_TWO_KEYS.contains(key))
+                }));
+            }
+        }
+        bindings.gamepad = gamepads
+            .0
+            .get(index as usize)
+            .map_or(GamepadFilter::Disabled, |gamepad| {
+                GamepadFilter::Only(*gamepad)
+            });
+        bindings
+    }
+
+    /// Returns the inputs bound to an action
+    pub fn get(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings
+            .get(&action)
+            .map(|bindings| bindings.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Binds an input to an action, replacing the inputs of the same kind. Binding a key replaces
+    /// the action's keys, and binding a gamepad button replaces its buttons.
+    ///
+    /// Keys kept for the second player in local co-op can't be bound, as the first player would
+    /// lose them in co-op. Returns `false` without binding anything for those.
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) -> bool {
+        if matches!(binding, InputBinding::Key(key) if PLAYER_TWO_KEYS.contains(&key)) {
+            return false;
+        }
+        let bindings = self.bindings.entry(action).or_default();
+        bindings.retain(|b| !b.is_same_kind(&binding));
+        bindings.push(binding);
+        true
+    }
+
+    /// Binds the default inputs to actions that have none, such as actions added since the
+    /// bindings were saved
+    pub fn add_missing_defaults(&mut self) {
+        for (action, defaults) in Self::default().bindings {
+            self.bindings.entry(action).or_insert(defaults);
+        }
+    }
+
+    /// Returns how far an action is pressed, from `0` to `1`, using the strongest bound input
+    pub fn value(
+        &self,
+        action: InputAction,
+        keys: &ButtonInput<KeyCode>,
+        gamepads: &Gamepads,
+        buttons: &ButtonInput<GamepadButton>,
+        axes: &Axis<GamepadAxis>,
+    ) -> f32 {
+        self.get(action)
+            .iter()
+            .map(|binding| binding.value(keys, gamepads, buttons, axes, self.gamepad))
+            .fold(0., f32::max)
+    }
+}
+
+/// Keys only used by the second player in local co-op
+const PLAYER_TWO_KEYS: [KeyCode; 6] = [
+    KeyCode::ArrowUp,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowRight,
+    KeyCode::ShiftRight,
+    KeyCode::ControlRight,
+];
+
+/// Connected gamepads, in the order they were connected. Gamepad ids aren't guaranteed to start at
+/// `0` or to have no gaps, so co-op players are given gamepads in this order instead.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct ConnectedGamepads(pub Vec<Gamepad>);
+
+/// The action waiting for a new input on the settings screen. While re-binding, and until the
+/// new input is released, every action is read as released so the press doesn't also trigger one.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct Rebinding {
+    pub action: Option<InputAction>,
+    /// The last key pressed for the action, if it couldn't be bound as it is kept for the second
+    /// player
+    pub rejected: Option<KeyCode>,
+    /// Set once the new input is bound, until every key and button is released
+    pub waiting_for_release: bool,
+}
+
+impl Rebinding {
+    pub fn is_active(&self) -> bool {
+        self.action.is_some() || self.waiting_for_release
+    }
+}
+
+pub fn is_rebinding(rebinding: Res<Rebinding>) -> bool {
+    rebinding.is_active()
+}
+
+/// The state of every [`InputAction`] this frame. Systems that take input should read this
+/// instead of reading devices directly.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct ActionState {
+    values: HashMap<InputAction, f32>,
+    previous_values: HashMap<InputAction, f32>,
+}
+
+impl ActionState {
+    /// Returns how far an action is pressed, from `0` to `1`
+    pub fn value(&self, action: InputAction) -> f32 {
+        self.values.get(&action).copied().unwrap_or_default()
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.value(action) >= PRESS_THRESHOLD
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        let previous = self
+            .previous_values
+            .get(&action)
+            .copied()
+            .unwrap_or_default();
+        self.pressed(action) && previous < PRESS_THRESHOLD
+    }
+
+    /// Raises the value of an action for this frame, for inputs that aren't read from
+    /// [`InputBindings`], such as on-screen controls
+    pub fn press(&mut self, action: InputAction, value: f32) {
+        let value = self.value(action).max(value);
+        self.values.insert(action, value);
+    }
+
+    /// Stores the new value of an action, keeping the last one to detect presses
+    pub fn set(&mut self, action: InputAction, value: f32) {
+        let previous = self.value(action);
+        self.previous_values.insert(action, previous);
+        self.values.insert(action, value);
+    }
+}
+
+/// The inputs of a single player. Menus read [`ActionState`], which accepts input from every
+/// device, while players read their own bindings so they can share a keyboard in local co-op.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct PlayerInput {
+    pub bindings: InputBindings,
+    pub actions: ActionState,
+}
+
+fn update_connected_gamepads(
+    mut events: EventReader<GamepadConnectionEvent>,
+    mut connected: ResMut<ConnectedGamepads>,
+) {
+    for event in events.read() {
+        connected.0.retain(|gamepad| *gamepad != event.gamepad);
+        if event.connected() {
+            connected.0.push(event.gamepad);
+        }
+    }
+}
+
+fn load_input_bindings(
+    mut commands: Commands,
+    storage: Res<GameStorage>,
+    registry: Res<AppTypeRegistry>,
+) {
+    let saved = storage.load_reflect::<InputBindings>(InputBindings::STORAGE_KEY, &registry.read());
+    if let Some(mut bindings) = saved {
+        bindings.add_missing_defaults();
+        commands.insert_resource(bindings);
+    }
+}
+
+/// System that binds the first key or gamepad button pressed to the action being re-bound
+fn capture_rebinding(
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    storage: Res<GameStorage>,
+    registry: Res<AppTypeRegistry>,
+) {
+    if rebinding.waiting_for_release {
+        if keys.get_pressed().next().is_none() && buttons.get_pressed().next().is_none() {
+            rebinding.waiting_for_release = false;
+        }
+        return;
+    }
+
+    let Some(action) = rebinding.action else {
+        return;
+    };
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| InputBinding::Key(*key))
+        .or_else(|| {
+            buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Button(button.button_type))
+        });
+    let Some(binding) = binding else {
+        return;
+    };
+
+    if !bindings.rebind(action, binding) {
+        if let InputBinding::Key(key) = binding {
+            rebinding.rejected = Some(key);
+        }
+        return;
+    }
+    storage.save_reflect(InputBindings::STORAGE_KEY, &*bindings, &registry.read());
+    *rebinding = Rebinding {
+        waiting_for_release: true,
+        ..default()
+    };
+}
+
+pub fn update_action_state(
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    mut state: ResMut<ActionState>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+) {
+    for action in InputAction::ALL {
+        let value = if rebinding.is_active() {
+            0.
+        } else {
+            bindings.value(action, &keys, &gamepads, &buttons, &axes)
+        };
+        state.set(action, value);
+    }
+}
+
+pub fn update_player_input(
+    mut query: Query<&mut PlayerInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+) {
+    for mut input in query.iter_mut() {
+        let input = input.as_mut();
+        for action in InputAction::ALL {
+            let value = input
+                .bindings
+                .value(action, &keys, &gamepads, &buttons, &axes);
+            input.actions.set(action, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns how far `action` is pressed with `keys` held down
+    fn key_value(bindings: &InputBindings, action: InputAction, keys: &[KeyCode]) -> f32 {
+        let mut input = ButtonInput::<KeyCode>::default();
+        for key in keys {
+            input.press(*key);
+        }
+        bindings.value(
+            action,
+            &input,
+            &Gamepads::default(),
+            &ButtonInput::default(),
+            &Axis::default(),
+        )
+    }
+
+    #[test]
+    fn bindings_read_their_keys() {
+        struct TestCase {
+            name: &'static str,
+            bindings: InputBindings,
+            keys: Vec<KeyCode>,
+            expected: f32,
+        }
+        let mut rebound = InputBindings::default();
+        rebound.rebind(InputAction::MoveUp, InputBinding::Key(KeyCode::KeyI));
+        let connected = ConnectedGamepads::default();
+        let test_cases = [
+            TestCase {
+                name: "WASD is bound by default",
+                bindings: InputBindings::default(),
+                keys: vec![KeyCode::KeyW],
+                expected: 1.,
+            },
+            TestCase {
+                name: "arrow keys are bound by default",
+                bindings: InputBindings::default(),
+                keys: vec![KeyCode::ArrowUp],
+                expected: 1.,
+            },
+            TestCase {
+                name: "other keys aren't bound",
+                bindings: InputBindings::default(),
+                keys: vec![KeyCode::KeyI],
+                expected: 0.,
+            },
+            TestCase {
+                name: "a re-bound key replaces the default keys",
+                bindings: rebound.clone(),
+                keys: vec![KeyCode::KeyW, KeyCode::ArrowUp],
+                expected: 0.,
+            },
+            TestCase {
+                name: "a re-bound key is read",
+                bindings: rebound.clone(),
+                keys: vec![KeyCode::KeyI],
+                expected: 1.,
+            },
+            TestCase {
+                name: "player 1 doesn't use player 2's keys in co-op",
+                bindings: InputBindings::default().for_player(0, 2, &connected),
+                keys: vec![KeyCode::ArrowUp],
+                expected: 0.,
+            },
+            TestCase {
+                name: "player 1 keeps their re-bound keys in co-op",
+                bindings: rebound.for_player(0, 2, &connected),
+                keys: vec![KeyCode::KeyI],
+                expected: 1.,
+            },
+            TestCase {
+                name: "player 2 doesn't use player 1's keys in co-op",
+                bindings: rebound.for_player(1, 2, &connected),
+                keys: vec![KeyCode::KeyW, KeyCode::KeyI],
+                expected: 0.,
+            },
+            TestCase {
+                name: "player 2 keeps the arrow keys whatever player 1 re-binds",
+                bindings: rebound.for_player(1, 2, &connected),
+                keys: vec![KeyCode::ArrowUp],
+                expected: 1.,
+            },
+        ];
+        for test in test_cases {
+            let value = key_value(&test.bindings, InputAction::MoveUp, &test.keys);
+            assert_eq!(value, test.expected, "{}", test.name);
+        }
+    }
+
+    #[test]
+    fn action_state_detects_presses() {
+        let mut state = ActionState::default();
+        state.set(InputAction::MoveUp, 1.);
+        assert!(state.pressed(InputAction::MoveUp));
+        assert!(state.just_pressed(InputAction::MoveUp));
+
+        // held down, so no longer just pressed
+        state.set(InputAction::MoveUp, 1.);
+        assert!(state.pressed(InputAction::MoveUp));
+        assert!(!state.just_pressed(InputAction::MoveUp));
+
+        // a light press is below the threshold
+        state.set(InputAction::MoveUp, 0.);
+        state.set(InputAction::MoveUp, PRESS_THRESHOLD / 2.);
+        assert!(!state.pressed(InputAction::MoveUp));
+    }
+
+    #[test]
+    fn rebinding_replaces_inputs_of_the_same_kind() {
+        let mut bindings = InputBindings::default();
+
+        // a key replaces the keys, but keeps the gamepad inputs
+        bindings.rebind(InputAction::MoveUp, InputBinding::Key(KeyCode::KeyI));
+        let expected = [
+            InputBinding::Button(GamepadButtonType::DPadUp),
+            InputBinding::Stick {
+                axis: GamepadAxisType::LeftStickY,
+                positive: true,
+            },
+            InputBinding::Key(KeyCode::KeyI),
+        ];
+        assert_eq!(bindings.get(InputAction::MoveUp), expected);
+
+        // a button replaces the buttons, but keeps the keys and sticks
+        bindings.rebind(
+            InputAction::MoveUp,
+            InputBinding::Button(GamepadButtonType::North),
+        );
+        let expected = [
+            InputBinding::Stick {
+                axis: GamepadAxisType::LeftStickY,
+                positive: true,
+            },
+            InputBinding::Key(KeyCode::KeyI),
+            InputBinding::Button(GamepadButtonType::North),
+        ];
+        assert_eq!(bindings.get(InputAction::MoveUp), expected);
+    }
+
+    #[test]
+    fn players_get_gamepads_in_connection_order() {
+        struct TestCase {
+            index: u32,
+            player_count: u32,
+            connected: Vec<Gamepad>,
+            expected: GamepadFilter,
+        }
+        let test_cases = [
+            // a single player can use any gamepad
+            TestCase {
+                index: 0,
+                player_count: 1,
+                connected: vec![Gamepad::new(3)],
+                expected: GamepadFilter::Any,
+            },
+            TestCase {
+                index: 0,
+                player_count: 2,
+                connected: vec![Gamepad::new(3), Gamepad::new(0)],
+                expected: GamepadFilter::Only(Gamepad::new(3)),
+            },
+            TestCase {
+                index: 1,
+                player_count: 2,
+                connected: vec![Gamepad::new(3), Gamepad::new(0)],
+                expected: GamepadFilter::Only(Gamepad::new(0)),
+            },
+            // players without a gamepad don't read anyone else's
+            TestCase {
+                index: 1,
+                player_count: 2,
+                connected: vec![Gamepad::new(3)],
+                expected: GamepadFilter::Disabled,
+            },
+        ];
+        for test in test_cases {
+            let connected = ConnectedGamepads(test.connected);
+            let bindings =
+                InputBindings::default().for_player(test.index, test.player_count, &connected);
+            assert_eq!(bindings.gamepad, test.expected);
+        }
+    }
+
+    #[test]
+    fn bindings_show_short_names() {
+        let test_cases = [
+            (InputBinding::Key(KeyCode::KeyI), "I"),
+            (InputBinding::Key(KeyCode::Digit1), "1"),
+            (InputBinding::Key(KeyCode::ArrowUp), "Up"),
+            (InputBinding::Key(KeyCode::ShiftLeft), "ShiftLeft"),
+            (InputBinding::Button(GamepadButtonType::South), "South"),
+            (
+                InputBinding::Stick {
+                    axis: GamepadAxisType::LeftStickX,
+                    positive: false,
+                },
+                "LeftStickX-",
+            ),
+        ];
+        for (binding, expected) in test_cases {
+            assert_eq!(binding.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn saved_bindings_load_with_missing_defaults() {
+        let storage = GameStorage(Box::new(crate::MemoryStorage::default()));
+        let mut registry = bevy::reflect::TypeRegistry::default();
+        registry.register::<InputBindings>();
+
+        let mut bindings = InputBindings::default();
+        bindings.rebind(InputAction::MoveUp, InputBinding::Key(KeyCode::KeyI));
+        bindings.bindings.remove(&InputAction::Confirm);
+        storage.save_reflect(InputBindings::STORAGE_KEY, &bindings, &registry);
+
+        let mut loaded = storage
+            .load_reflect::<InputBindings>(InputBindings::STORAGE_KEY, &registry)
+            .unwrap();
+        assert_eq!(
+            loaded.get(InputAction::MoveUp),
+            bindings.get(InputAction::MoveUp)
+        );
+        assert!(loaded.get(InputAction::Confirm).is_empty());
+
+        loaded.add_missing_defaults();
+        assert_eq!(
+            loaded.get(InputAction::Confirm),
+            InputBindings::default().get(InputAction::Confirm)
+        );
+    }
+
+    #[test]
+    fn player_two_keys_cannot_be_rebound() {
+        let mut bindings = InputBindings::default();
+        let connected = ConnectedGamepads::default();
+
+        for key in PLAYER_TWO_KEYS {
+            assert!(!bindings.rebind(InputAction::Ultimate, InputBinding::Key(key)));
+        }
+        assert_eq!(
+            bindings.get(InputAction::Ultimate),
+            InputBindings::default().get(InputAction::Ultimate)
+        );
+
+        // so every key player 1 binds is kept in co-op
+        assert!(bindings.rebind(InputAction::Ultimate, InputBinding::Key(KeyCode::KeyQ)));
+        let player_one = bindings.for_player(0, 2, &connected);
+        assert!(player_one
+            .get(InputAction::Ultimate)
+            .contains(&InputBinding::Key(KeyCode::KeyQ)));
+        let player_two = bindings.for_player(1, 2, &connected);
+        assert!(player_two
+            .get(InputAction::Ultimate)
+            .contains(&InputBinding::Key(KeyCode::ControlRight)));
+    }
+}
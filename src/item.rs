@@ -0,0 +1,289 @@
+use std::fmt;
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::*;
+
+pub struct ItemPlugin;
+
+impl Plugin for ItemPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ItemResources>().add_systems(
+            Update,
+            (
+                setup_new_explosions,
+                explode_orb_kills.run_if(on_event::<EnemyKilled>()),
+            )
+                .run_if(in_game),
+        );
+    }
+}
+
+/// A passive item held by a player. Unlike stat power ups, items change how the game plays by
+/// hooking into gameplay events. A player can only hold [`constants::PLAYER_ITEM_SLOTS`] items.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum Item {
+    /// Enemies killed by orbs explode, damaging the enemies around them
+    VolatileOrbs,
+    /// Every point collected heals
+    Siphon,
+    /// Every few projectiles fired by the blaster is a critical hit
+    Sharpshooter,
+}
+
+impl Item {
+    pub const ALL: [Self; 3] = [Self::VolatileOrbs, Self::Siphon, Self::Sharpshooter];
+
+    pub fn description(&self) -> String {
+        match self {
+            Item::VolatileOrbs => "Enemies killed by orbs explode".to_string(),
+            Item::Siphon => format!("Each point collected heals {}", constants::ITEM_SIPHON_HEAL),
+            Item::Sharpshooter => format!(
+                "Every {}th projectile is critical, dealing {}x damage",
+                constants::ITEM_CRITICAL_EVERY,
+                constants::ITEM_CRITICAL_MULTIPLIER
+            ),
+        }
+    }
+
+    /// Returns an item to offer a player in place of a stat power up, or `None` if their slots are
+    /// full, they hold every item, or the roll fails
+    pub fn new_random_offer(stats: &PlayerStats, rng: &mut impl Rng) -> Option<Self> {
+        if stats.items.len() >= constants::PLAYER_ITEM_SLOTS
+            || !rng.gen_bool(constants::ITEM_OFFER_CHANCE)
+        {
+            return None;
+        }
+        let items: Vec<Item> = Self::ALL
+            .into_iter()
+            .filter(|item| !stats.has_item(*item))
+            .collect();
+        items.choose(rng).copied()
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Item::VolatileOrbs => write!(f, "Volatile Orbs"),
+            Item::Siphon => write!(f, "Siphon"),
+            Item::Sharpshooter => write!(f, "Sharpshooter"),
+        }
+    }
+}
+
+/// Counts the events that items trigger on, for each player
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct ItemTriggers {
+    pub projectiles_fired: u32,
+}
+
+impl ItemTriggers {
+    /// Counts a projectile fired by the blaster. Returns `true` if it is a critical hit.
+    pub fn fire_projectile(&mut self) -> bool {
+        self.projectiles_fired += 1;
+        self.projectiles_fired
+            .is_multiple_of(constants::ITEM_CRITICAL_EVERY)
+    }
+}
+
+/// Flash left behind by an enemy exploding
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Explosion;
+
+#[derive(Bundle)]
+pub struct ExplosionBundle {
+    pub name: Name,
+    pub explosion: Explosion,
+    pub state_scoped: StateScoped<AppState>,
+    pub spatial: SpatialBundle,
+    pub lifetime: Lifetime,
+}
+
+impl ExplosionBundle {
+    pub fn new(pos: Vec3) -> Self {
+        Self {
+            name: Name::new("Explosion"),
+            explosion: Explosion,
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::from_transform(
+                Transform::from_translation(pos)
+                    .with_scale(Vec3::splat(constants::ITEM_EXPLOSION_RADIUS)),
+            ),
+            lifetime: Lifetime::from_seconds(constants::ITEM_EXPLOSION_LIFETIME_SECS),
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct ItemResources {
+    pub mesh: Option<Handle<Mesh>>,
+    pub material: Option<Handle<StandardMaterial>>,
+}
+
+impl ItemResources {
+    pub fn get_or_create_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(ref material) = self.material {
+            material.clone()
+        } else {
+            let material = materials.add(StandardMaterial {
+                base_color: Color::srgba(1., 0.5, 0., 0.5),
+                emissive: LinearRgba::rgb(13.99, 4.0, 1.0),
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            });
+            self.material = Some(material.clone());
+            material
+        }
+    }
+
+    pub fn get_or_create_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        if let Some(ref mesh) = self.mesh {
+            mesh.clone()
+        } else {
+            // unit sphere, scaled to the explosion radius
+            let mesh = meshes.add(Sphere::new(1.));
+            self.mesh = Some(mesh.clone());
+            mesh
+        }
+    }
+}
+
+fn setup_new_explosions(
+    mut commands: Commands,
+    query: Query<Entity, Added<Explosion>>,
+    mut item_resources: ResMut<ItemResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for entity in query.iter() {
+        let material = item_resources.get_or_create_material(&mut materials);
+        let mesh = item_resources.get_or_create_mesh(&mut meshes);
+        commands.entity(entity).insert((material, mesh));
+    }
+}
+
+/// System that makes enemies killed by the orbs of a player holding [`Item::VolatileOrbs`] explode
+fn explode_orb_kills(
+    mut commands: Commands,
+    mut events: EventReader<EnemyKilled>,
+    stats_query: Query<&PlayerStats>,
+    mut enemy_query: Query<(Entity, &GlobalTransform, &mut Health), With<Enemy>>,
+    mut damage_writer: EventWriter<DamageEvent>,
+) {
+    for event in events.read() {
+        let Some(DamageSource::Orb(owner)) = event.source else {
+            continue;
+        };
+        let Ok(stats) = stats_query.get(owner) else {
+            continue;
+        };
+        if !stats.has_item(Item::VolatileOrbs) {
+            continue;
+        }
+
+        commands.spawn(ExplosionBundle::new(event.position));
+        let damage = stats.get_damage(constants::ITEM_EXPLOSION_DAMAGE);
+        for (enemy, transform, mut health) in enemy_query.iter_mut() {
+            if health.current > 0.
+                && transform.translation().distance(event.position)
+                    <= constants::ITEM_EXPLOSION_RADIUS
+            {
+                // explosion kills aren't orb kills, so they don't set off more explosions
+                health.damage(damage, DamageSource::Explosion);
+                damage_writer.send(DamageEvent {
+                    target: enemy,
+                    amount: damage,
+                    source: DamageSource::Explosion,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn items_fill_limited_slots_without_repeats() {
+        struct TestCase {
+            name: &'static str,
+            held: Vec<Item>,
+            add: Item,
+            expected: Vec<Item>,
+        }
+        let test_cases = [
+            TestCase {
+                name: "an empty slot takes the item",
+                held: vec![Item::Siphon],
+                add: Item::Sharpshooter,
+                expected: vec![Item::Siphon, Item::Sharpshooter],
+            },
+            TestCase {
+                name: "a held item isn't added again",
+                held: vec![Item::Siphon],
+                add: Item::Siphon,
+                expected: vec![Item::Siphon],
+            },
+            TestCase {
+                name: "nothing is added once every slot is full",
+                held: vec![Item::Siphon, Item::Sharpshooter],
+                add: Item::VolatileOrbs,
+                expected: vec![Item::Siphon, Item::Sharpshooter],
+            },
+        ];
+        for test in test_cases {
+            let mut stats = PlayerStats::default();
+            for item in test.held {
+                stats.add_item(item);
+            }
+            stats.add_item(test.add);
+            assert_eq!(stats.items, test.expected, "{}", test.name);
+        }
+    }
+
+    #[test]
+    fn item_offers_skip_held_items_and_full_slots() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut stats = PlayerStats::default();
+        for _ in 0..100 {
+            if let Some(item) = Item::new_random_offer(&stats, &mut rng) {
+                assert!(!stats.has_item(item));
+                stats.add_item(item);
+            }
+        }
+        assert_eq!(stats.items.len(), constants::PLAYER_ITEM_SLOTS);
+        for _ in 0..100 {
+            assert_eq!(Item::new_random_offer(&stats, &mut rng), None);
+        }
+    }
+
+    #[test]
+    fn sharpshooter_crits_every_few_projectiles() {
+        let every = constants::ITEM_CRITICAL_EVERY;
+        let test_cases = [
+            (every - 1, 0),
+            (every, 1),
+            (every * 3, 3),
+            (every * 3 + 1, 3),
+        ];
+        for (projectiles, expected) in test_cases {
+            let mut triggers = ItemTriggers::default();
+            let critical_hits = (0..projectiles)
+                .filter(|_| triggers.fire_projectile())
+                .count();
+            assert_eq!(critical_hits, expected, "{} projectiles", projectiles);
+        }
+    }
+}
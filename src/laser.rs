@@ -0,0 +1,216 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::*;
+
+use self::math::get_angle_for_arc_length;
+
+pub struct LaserPlugin;
+
+impl Plugin for LaserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaserResources>().add_systems(
+            Update,
+            (
+                setup_new_lasers,
+                (update_laser_transform, tick_lasers)
+                    .chain()
+                    .run_if(not_paused),
+                update_lasers_on_stats_change,
+            )
+                .run_if(in_game),
+        );
+    }
+}
+
+/// A continuous beam along a great circle from the player. It sweeps around the player, starting
+/// from [`Player::up`], and damages every enemy it overlaps on each tick.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct Laser {
+    /// Angle around the player's position, measured from the player's up direction
+    pub angle: f32,
+    pub arc: GreatCircleArc,
+    pub half_width: f32,
+    pub tick_timer: Timer,
+}
+
+impl Laser {
+    pub fn new(angle: f32) -> Self {
+        Self {
+            angle,
+            arc: GreatCircleArc::new(Vec3::Z, Vec3::Y, 0.),
+            half_width: 0.,
+            tick_timer: Timer::from_seconds(constants::LASER_TICK_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct LaserBundle {
+    pub name: Name,
+    pub laser: Laser,
+    pub owned_by: OwnedBy,
+    pub state_scoped: StateScoped<AppState>,
+    pub spatial: SpatialBundle,
+    pub collision_groups: CollisionGroups,
+}
+
+impl LaserBundle {
+    pub fn new(owner: Entity, angle: f32) -> Self {
+        Self {
+            name: Name::new("Laser"),
+            laser: Laser::new(angle),
+            owned_by: OwnedBy(owner),
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::default(),
+            collision_groups: CollisionGroups::new(GROUP_PROJECTILE, GROUP_ENEMY),
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct LaserResources {
+    pub mesh: Option<Handle<Mesh>>,
+    pub material: Option<Handle<StandardMaterial>>,
+}
+
+impl LaserResources {
+    pub fn get_or_create_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(ref material) = self.material {
+            material.clone()
+        } else {
+            let material = materials.add(StandardMaterial {
+                emissive: LinearRgba::rgb(13.99, 0.0, 13.99),
+                ..default()
+            });
+            self.material = Some(material.clone());
+            material
+        }
+    }
+
+    pub fn get_or_create_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        if let Some(ref mesh) = self.mesh {
+            mesh.clone()
+        } else {
+            // unit cube, scaled to the length and width of the beam
+            let mesh = meshes.add(Cuboid::from_length(1.));
+            self.mesh = Some(mesh.clone());
+            mesh
+        }
+    }
+}
+
+fn setup_new_lasers(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), Added<Laser>>,
+    mut laser_resources: ResMut<LaserResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, transform) in query.iter() {
+        commands.entity(entity).insert(MaterialMeshBundle {
+            mesh: laser_resources.get_or_create_mesh(&mut meshes),
+            material: laser_resources.get_or_create_material(&mut materials),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
+
+fn update_lasers_on_stats_change(
+    mut commands: Commands,
+    query: Query<(Entity, &OwnedBy), With<Laser>>,
+    player_query: Query<(Entity, &PlayerStats), Changed<PlayerStats>>,
+) {
+    for (player_entity, stats) in player_query.iter() {
+        let owned_lasers = || {
+            query
+                .iter()
+                .filter(move |(_, owned_by)| owned_by.0 == player_entity)
+                .map(|(e, _)| e)
+        };
+        if owned_lasers().count() as u32 == stats.laser_beams {
+            continue;
+        }
+
+        // de-spawn existing lasers so the new ones are spaced evenly around the player
+        for e in owned_lasers() {
+            commands.entity(e).despawn_recursive();
+        }
+
+        let angle_spacer = TAU / stats.laser_beams.max(1) as f32;
+        for index in 0..stats.laser_beams {
+            commands.spawn(LaserBundle::new(player_entity, index as f32 * angle_spacer));
+        }
+    }
+}
+
+/// System that sweeps lasers around their player and updates their arc and visual transform
+fn update_laser_transform(
+    time: Res<Time>,
+    mut query: Query<(&mut Laser, &OwnedBy, &mut Transform)>,
+    player_query: Query<(&Player, &GlobalTransform, &PlayerStats)>,
+) {
+    let height = constants::PLANET_RADIUS + constants::LASER_HEIGHT;
+
+    for (mut laser, owned_by, mut transform) in query.iter_mut() {
+        let Ok((player, player_transform, stats)) = player_query.get(owned_by.0) else {
+            continue;
+        };
+        let normal = player_transform.translation().normalize();
+        let length = stats.get_attack_size(constants::LASER_BASE_LENGTH);
+        let width = stats.get_attack_size(constants::LASER_BASE_WIDTH);
+
+        laser.angle = (laser.angle + constants::LASER_ROTATION_SPEED * time.delta_seconds()) % TAU;
+
+        // rotate the player's up direction around the surface normal to get the beam direction
+        let direction = Quat::from_axis_angle(normal, laser.angle) * player.up.normalize();
+        laser.arc =
+            GreatCircleArc::new(normal, direction, get_angle_for_arc_length(length, height));
+        laser.half_width = width / 2.;
+
+        // draw the beam as a straight line between both ends of the arc
+        let start = laser.arc.start * height;
+        let end = laser.arc.end() * height;
+        *transform = Transform::from_translation((start + end) / 2.)
+            .looking_to(end - start, normal)
+            .with_scale(Vec3::new(width, width, start.distance(end)));
+    }
+}
+
+fn tick_lasers(
+    time: Res<Time>,
+    mut query: Query<(&mut Laser, &OwnedBy, &CollisionGroups)>,
+    collider_query: Query<(Entity, &Collider, &GlobalTransform, &CollisionGroups)>,
+    mut health_query: Query<&mut Health>,
+    stats_query: Query<&PlayerStats>,
+    mut damage_writer: EventWriter<DamageEvent>,
+) {
+    for (mut laser, owned_by, groups) in query.iter_mut() {
+        laser.tick_timer.tick(time.delta());
+        if !laser.tick_timer.just_finished() {
+            continue;
+        }
+
+        let Ok(stats) = stats_query.get(owned_by.0) else {
+            continue;
+        };
+        let damage = stats.get_damage(constants::LASER_BASE_DAMAGE);
+        for entity in query_arc(&laser.arc, laser.half_width, groups, &collider_query) {
+            if let Ok(mut health) = health_query.get_mut(entity) {
+                health.damage(damage, DamageSource::Laser(owned_by.0));
+                damage_writer.send(DamageEvent {
+                    target: entity,
+                    amount: damage,
+                    source: DamageSource::Laser(owned_by.0),
+                });
+            }
+        }
+    }
+}
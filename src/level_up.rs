@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 
 use crate::*;
@@ -6,12 +8,71 @@ pub struct LevelUpPlugin;
 
 impl Plugin for LevelUpPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_level_ups.run_if(in_game_not_paused));
+        app.init_resource::<PendingPowerUps>()
+            .add_systems(OnEnter(AppState::Game), reset_pending_power_ups)
+            .add_systems(Update, handle_level_ups.run_if(in_game_not_paused));
+    }
+}
+
+/// Power ups still to be picked, in the order they get picked. A player has one entry for each
+/// level gained.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct PendingPowerUps {
+    pub queue: VecDeque<PendingPowerUp>,
+}
+
+impl PendingPowerUps {
+    /// Returns the pick at the front of the queue
+    pub fn front(&self) -> Option<&PendingPowerUp> {
+        self.queue.front()
     }
 }
 
-fn handle_level_ups(mut next_state: ResMut<NextState<GameState>>, mut score: ResMut<PlayerScore>) {
-    if score.get_level_up() {
-        next_state.set(GameState::PowerUp);
+/// A power up a player gets to pick for gaining a level
+#[derive(Debug, Clone, Reflect)]
+pub struct PendingPowerUp {
+    pub player: Entity,
+    /// The level gained
+    pub level: u32,
+    /// Which of the player's picks this is, starting from `1`
+    pub pick: u32,
+    /// Number of picks the player has queued since the game was paused to pick them
+    pub picks: u32,
+}
+
+fn reset_pending_power_ups(mut pending: ResMut<PendingPowerUps>) {
+    *pending = PendingPowerUps::default();
+}
+
+fn handle_level_ups(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut score: ResMut<PlayerScore>,
+    mut pending: ResMut<PendingPowerUps>,
+    curve: Res<XpCurve>,
+    mut query: Query<(Entity, &Player, &mut PlayerStats)>,
+) {
+    let levels = score.take_level_ups(&curve);
+    if levels == 0 {
+        return;
+    }
+
+    let mut players: Vec<_> = query.iter_mut().collect();
+    players.sort_by_key(|(_, player, _)| player.index);
+    let first_level = score.level + 1 - levels;
+    for pick in 1..=levels {
+        for (entity, player, stats) in players.iter_mut() {
+            // the character's passive bonus grows with every level
+            let passive = &player.character.character().passive;
+            stats.add_modifier(passive.stat, passive.value, ModifierSource::Passive);
+            // every living player gets to pick a power up
+            pending.queue.push_back(PendingPowerUp {
+                player: *entity,
+                level: first_level + pick - 1,
+                pick,
+                picks: levels,
+            });
+        }
     }
+    next_state.set(GameState::PowerUp);
 }
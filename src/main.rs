@@ -58,6 +58,12 @@ use lifetime::*;
 mod hud_ui;
 use hud_ui::*;
 
+mod input_actions;
+use input_actions::*;
+
+mod virtual_joystick;
+use virtual_joystick::*;
+
 mod ui_widgets;
 use ui_widgets::*;
 
@@ -73,6 +79,36 @@ use level_up::*;
 mod orb;
 use orb::*;
 
+mod ally;
+use ally::*;
+
+mod laser;
+use laser::*;
+
+mod dash;
+use dash::*;
+
+mod ultimate;
+use ultimate::*;
+
+mod item;
+use item::*;
+
+mod achievement;
+use achievement::*;
+
+mod storage;
+use storage::*;
+
+mod run_snapshot;
+use run_snapshot::*;
+
+mod settings;
+use settings::*;
+
+mod run_record;
+use run_record::*;
+
 mod constants;
 mod math;
 
@@ -102,8 +138,27 @@ fn main() {
             AttractorPlugin,
             GameResourcesPlugin,
         ))
-        .add_plugins((LevelUpPlugin, OrbPlugin))
-        .add_plugins((MenuPlugin, UiWidgetsPlugin, HudUIPlugin))
+        .add_plugins((
+            LevelUpPlugin,
+            OrbPlugin,
+            AllyPlugin,
+            LaserPlugin,
+            DashPlugin,
+            UltimatePlugin,
+            StoragePlugin,
+            AchievementPlugin,
+            ItemPlugin,
+            RunSnapshotPlugin,
+            SettingsPlugin,
+            RunRecordPlugin,
+        ))
+        .add_plugins((
+            MenuPlugin,
+            UiWidgetsPlugin,
+            HudUIPlugin,
+            InputActionsPlugin,
+            VirtualJoystickPlugin,
+        ))
         .init_state::<AppState>()
         .init_state::<GameState>()
         .enable_state_scoped_entities::<AppState>()
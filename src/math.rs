@@ -1,3 +1,5 @@
+use bevy::prelude::*;
+
 pub fn get_angle_for_arc_length(arc_length: f32, radius: f32) -> f32 {
     // Arc length = rθ × π/180
     // arc_length = radius * angle
@@ -5,9 +7,72 @@ pub fn get_angle_for_arc_length(arc_length: f32, radius: f32) -> f32 {
     arc_length / radius
 }
 
+/// Clamps a movement input so diagonals are no faster than straight lines, while keeping the
+/// magnitude of analog input below `1`.
+pub fn clamp_movement_input(input: Vec2) -> Vec2 {
+    input.clamp_length_max(1.)
+}
+
+/// Returns the rotation around the center of the planet that moves an object at `normal` in the
+/// direction of `input`. The input is in the tangent frame defined by `up`, with `+x` to the right
+/// and `+y` up. An input of length `1` moves `max_angle` radians.
+pub fn get_movement_rotation(normal: Vec3, up: Vec3, input: Vec2, max_angle: f32) -> Quat {
+    let input = clamp_movement_input(input);
+    if input == Vec2::ZERO {
+        return Quat::IDENTITY;
+    }
+
+    let normal = normal.normalize();
+    let up = up.normalize();
+    let right = up.cross(normal).normalize();
+
+    // rotating around this axis moves the object towards the direction
+    let direction = right * input.x + up * input.y;
+    let axis = normal.cross(direction).normalize();
+
+    Quat::from_axis_angle(axis, max_angle * input.length())
+}
+
+/// Returns the input that moves an object at `normal` along the great circle towards `target`. The
+/// input is in the same tangent frame as [`get_movement_rotation`], and has a length of `1`, or `0`
+/// if the object is already at the target.
+pub fn get_input_towards(normal: Vec3, up: Vec3, target: Vec3) -> Vec2 {
+    let normal = normal.normalize();
+    let up = up.normalize();
+    let right = up.cross(normal).normalize();
+
+    // the part of the direction to the target that lies on the surface
+    let direction = target.normalize() - normal;
+    let input = Vec2::new(direction.dot(right), direction.dot(up));
+    input.normalize_or_zero()
+}
+
+/// Returns the closest point where a ray hits a sphere centered on the origin, if it hits at all.
+pub fn ray_sphere_intersection(origin: Vec3, direction: Vec3, radius: f32) -> Option<Vec3> {
+    let direction = direction.normalize();
+    // solve |origin + t * direction| = radius for t
+    let b = origin.dot(direction);
+    let c = origin.length_squared() - radius * radius;
+    let discriminant = b * b - c;
+    if discriminant < 0. {
+        return None;
+    }
+
+    let sqrt = discriminant.sqrt();
+    let t = if -b - sqrt >= 0. {
+        -b - sqrt
+    } else {
+        -b + sqrt
+    };
+    if t < 0. {
+        return None;
+    }
+    Some(origin + direction * t)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::f32::consts::{PI, TAU};
+    use std::f32::consts::{FRAC_1_SQRT_2, PI, TAU};
 
     use super::*;
 
@@ -17,4 +82,81 @@ mod tests {
         let circumference = 2. * PI * radius;
         assert_eq!(TAU, get_angle_for_arc_length(circumference, radius));
     }
+
+    #[test]
+    fn movement_speed_is_same_in_every_direction() {
+        let normal = Vec3::Z;
+        let up = Vec3::Y;
+        let max_angle = 0.1;
+
+        let directions = [
+            Vec2::new(0., 1.),
+            Vec2::new(0., -1.),
+            Vec2::new(1., 0.),
+            Vec2::new(-1., 0.),
+            Vec2::new(1., 1.),
+            Vec2::new(-1., 1.),
+            Vec2::new(1., -1.),
+            Vec2::new(-1., -1.),
+            Vec2::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+            Vec2::new(0.6, 0.8),
+        ];
+        for input in directions {
+            let rot = get_movement_rotation(normal, up, input, max_angle);
+            let moved = normal.angle_between(rot * normal);
+            assert!(
+                (moved - max_angle).abs() < 1e-5,
+                "input {:?} moved {} instead of {}",
+                input,
+                moved,
+                max_angle
+            );
+        }
+    }
+
+    #[test]
+    fn movement_follows_input_direction() {
+        let normal = Vec3::Z;
+        let up = Vec3::Y;
+
+        // up moves towards the up axis, right moves towards the right axis
+        let rot = get_movement_rotation(normal, up, Vec2::Y, 0.1);
+        assert!((rot * normal).y > 0.);
+        let rot = get_movement_rotation(normal, up, Vec2::X, 0.1);
+        assert!((rot * normal).x > 0.);
+
+        // analog input moves a fraction of the distance
+        let rot = get_movement_rotation(normal, up, Vec2::new(0., 0.5), 0.1);
+        let moved = normal.angle_between(rot * normal);
+        assert!((moved - 0.05).abs() < 1e-5);
+
+        // no input, no movement
+        assert_eq!(
+            get_movement_rotation(normal, up, Vec2::ZERO, 0.1),
+            Quat::IDENTITY
+        );
+    }
+
+    #[test]
+    fn ray_sphere_intersection_and_input_towards_work() {
+        // a ray pointing at the sphere hits the near side
+        let hit = ray_sphere_intersection(Vec3::new(0., 0., 10.), Vec3::NEG_Z, 2.).unwrap();
+        assert!(hit.distance(Vec3::new(0., 0., 2.)) < 1e-5);
+
+        // a ray pointing away or past the sphere misses
+        assert!(ray_sphere_intersection(Vec3::new(0., 0., 10.), Vec3::Z, 2.).is_none());
+        assert!(ray_sphere_intersection(Vec3::new(0., 3., 10.), Vec3::NEG_Z, 2.).is_none());
+
+        // the input points towards the target, and walking along it gets closer
+        let normal = Vec3::Z;
+        let up = Vec3::Y;
+        let target = Vec3::new(1., 1., 1.);
+        let input = get_input_towards(normal, up, target);
+        assert!((input - Vec2::ONE.normalize()).length() < 1e-5);
+        let rot = get_movement_rotation(normal, up, input, 0.1);
+        assert!((rot * normal).angle_between(target) < normal.angle_between(target));
+
+        // no input when already at the target
+        assert_eq!(get_input_towards(normal, up, normal), Vec2::ZERO);
+    }
 }
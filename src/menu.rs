@@ -1,13 +1,20 @@
+mod achievements;
+mod character_select;
 mod game_over;
+mod leaderboard;
 mod main_menu;
 mod pause_menu;
 mod plugin;
 mod power_up;
+mod seed;
+mod settings;
+mod shop;
 mod style;
 mod types;
 mod widgets;
 
 pub use self::{
-    game_over::*, main_menu::*, pause_menu::*, plugin::*, power_up::*, types::*, widgets::*,
+    achievements::*, character_select::*, game_over::*, leaderboard::*, main_menu::*,
+    pause_menu::*, plugin::*, power_up::*, seed::*, settings::*, shop::*, types::*, widgets::*,
 };
 use style::*;
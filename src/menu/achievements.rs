@@ -0,0 +1,86 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_ui_helpers::*;
+
+use crate::{game_state::*, *};
+
+use super::{power_up_panel, MENU_SPACER, TABLE_SPACER};
+
+pub struct AchievementsMenuPlugin;
+
+impl Plugin for AchievementsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Achievements), setup_menu);
+    }
+}
+
+fn setup_menu(mut commands: Commands, progress: Res<AchievementProgress>) {
+    root(
+        &mut commands,
+        StateScoped(AppState::Achievements),
+        (c_full_screen, c_center, c_col),
+    )
+    .with_children(|p| {
+        power_up_panel(p, |p| {
+            menu_title(
+                p,
+                format!(
+                    "Achievements ({} / {})",
+                    progress.unlocked.len(),
+                    Achievement::ALL.len()
+                ),
+            );
+            achievements_table(p, |p| {
+                for achievement in Achievement::ALL {
+                    achievement_row(p, achievement, progress.is_unlocked(achievement));
+                }
+            });
+        });
+        menu_button_widget(p, "Back", MenuButtonAction::MainMenu);
+    });
+}
+
+fn achievements_table(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    node(
+        parent,
+        (),
+        (
+            c_w_100,
+            c_margin_bottom(MENU_SPACER),
+            |b: &mut NodeBundle| {
+                b.style.display = Display::Grid;
+                b.style.grid_template_columns = vec![
+                    RepeatedGridTrack::fr(1, 1.),
+                    RepeatedGridTrack::fr(1, 2.),
+                    RepeatedGridTrack::fr(1, 2.),
+                ];
+            },
+        ),
+    )
+    .with_children(children);
+}
+
+fn achievement_row(parent: &mut ChildBuilder, achievement: Achievement, unlocked: bool) {
+    let color = if unlocked { css::GOLD } else { css::GRAY };
+    let reward = achievement
+        .reward()
+        .map(|reward| reward.to_string())
+        .unwrap_or_default();
+    let cells = [
+        achievement.to_string(),
+        achievement.goal().to_string(),
+        reward,
+    ];
+    for value in cells {
+        node(parent, (), c_padding(TABLE_SPACER)).with_children(|p| {
+            text(
+                p,
+                value,
+                (),
+                TextStyle {
+                    color: color.into(),
+                    ..default()
+                },
+            );
+        });
+    }
+}
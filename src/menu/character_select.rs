@@ -0,0 +1,179 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_ui_helpers::*;
+
+use crate::{game_state::*, *};
+
+use super::{power_up_layout, power_up_panel, CARD_SPACER};
+
+pub struct CharacterSelectMenuPlugin;
+
+impl Plugin for CharacterSelectMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CharacterChooser>()
+            .add_systems(
+                OnEnter(AppState::CharacterSelect),
+                (reset_character_chooser, setup_menu).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_character_button_clicked,
+                    (update_description_label, focus_current_character)
+                        .run_if(resource_changed::<CharacterChooser>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::CharacterSelect)),
+            );
+    }
+}
+
+/// Index of the player currently choosing a character
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct CharacterChooser(pub u32);
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct CharacterButton(pub CharacterKind);
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct CharacterDescriptionLabel;
+
+fn reset_character_chooser(mut chooser: ResMut<CharacterChooser>) {
+    chooser.0 = 0;
+}
+
+fn setup_menu(mut commands: Commands, achievement_progress: Res<AchievementProgress>) {
+    root(
+        &mut commands,
+        StateScoped(AppState::CharacterSelect),
+        (c_full_screen, c_center, c_col),
+    )
+    .with_children(|p| {
+        power_up_panel(p, |p| {
+            menu_title(p, "Choose Character");
+            node(p, (), c_margin_bottom(CARD_SPACER)).with_children(|p| {
+                text(p, "", CharacterDescriptionLabel, TextStyle::default());
+            });
+            power_up_layout(p, |p| {
+                for character in CHARACTERS.iter() {
+                    let locked_by = achievement_progress
+                        .required_for(AchievementReward::Character(character.kind));
+                    character_widget(p, character, locked_by);
+                }
+            });
+        });
+        menu_button_widget(p, "Back", MenuButtonAction::MainMenu);
+    });
+}
+
+/// Card for a character. Characters locked by an achievement show how to unlock them instead.
+fn character_widget(
+    parent: &mut ChildBuilder,
+    character: &Character,
+    locked_by: Option<Achievement>,
+) {
+    let name = Name::new(format!("Character: {:?}", character.kind));
+    let title = format!("{}", character.kind);
+    if let Some(achievement) = locked_by {
+        choice_card_widget(
+            parent,
+            name,
+            title,
+            format!("Locked\n\n{}: {}", achievement, achievement.goal()),
+            "Locked",
+            css::DIM_GRAY,
+            CharacterButton(character.kind),
+        );
+        return;
+    }
+    choice_card_widget(
+        parent,
+        name,
+        title,
+        format!(
+            "{}\n\nStarts with: {}\n\n{}",
+            character.description,
+            character.weapon,
+            character.passive_description(),
+        ),
+        "Choose",
+        character.color.darker(0.2),
+        CharacterButton(character.kind),
+    );
+}
+
+fn update_description_label(
+    chooser: Res<CharacterChooser>,
+    player_count: Res<PlayerCount>,
+    selected_characters: Res<SelectedCharacters>,
+    mut label_query: Query<&mut Text, With<CharacterDescriptionLabel>>,
+) {
+    let character = selected_characters.get(chooser.0);
+    for mut text in label_query.iter_mut() {
+        text.sections[0].value = if player_count.0 > 1 {
+            format!("Player {}: confirm to play as {}", chooser.0 + 1, character)
+        } else {
+            format!("Confirm to play as {}", character)
+        };
+    }
+}
+
+/// Focuses the last played character of the current player, so confirm picks it again
+fn focus_current_character(
+    mut commands: Commands,
+    chooser: Res<CharacterChooser>,
+    selected_characters: Res<SelectedCharacters>,
+    button_query: Query<(Entity, &CharacterButton)>,
+    focused_query: Query<Entity, With<Focused>>,
+) {
+    let character = selected_characters.get(chooser.0);
+    for entity in focused_query.iter() {
+        commands.entity(entity).remove::<Focused>();
+    }
+    for (entity, button) in button_query.iter() {
+        if button.0 == character {
+            commands.entity(entity).insert(Focused);
+        }
+    }
+}
+
+fn handle_character_button_clicked(
+    query: Query<(&CharacterButton, &Interaction), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut chooser: ResMut<CharacterChooser>,
+    mut selected_characters: ResMut<SelectedCharacters>,
+    player_count: Res<PlayerCount>,
+    achievement_progress: Res<AchievementProgress>,
+) {
+    for (button, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed
+            || !achievement_progress.is_character_unlocked(button.0)
+        {
+            continue;
+        }
+        choose_character(
+            button.0,
+            &mut next_state,
+            &mut chooser,
+            &mut selected_characters,
+            &player_count,
+        );
+    }
+}
+
+/// Sets the character of the current player, and moves on to the next player or starts the game
+fn choose_character(
+    character: CharacterKind,
+    next_state: &mut NextState<AppState>,
+    chooser: &mut CharacterChooser,
+    selected_characters: &mut SelectedCharacters,
+    player_count: &PlayerCount,
+) {
+    selected_characters.set(chooser.0, character);
+    chooser.0 += 1;
+    if chooser.0 >= player_count.0 {
+        next_state.set(AppState::Game);
+    }
+}
@@ -3,20 +3,90 @@ use bevy_ui_helpers::*;
 
 use crate::{game_state::*, *};
 
-use super::{MENU_SPACER, TABLE_SPACER};
+use super::{leaderboard_table, MENU_SPACER, TABLE_SPACER};
 
 pub struct GameOverMenuPlugin;
 
 impl Plugin for GameOverMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::GameOver), setup_menu);
+        app.init_resource::<NewHighScore>()
+            .add_systems(
+                OnEnter(AppState::GameOver),
+                (setup_new_high_score, setup_menu).chain(),
+            )
+            .add_systems(
+                Update,
+                handle_high_score_submitted.run_if(in_state(AppState::GameOver)),
+            );
     }
 }
 
-fn setup_menu(mut commands: Commands, score: Res<PlayerScore>, timer: Res<GameTimer>) {
-    root(
+/// The run that just ended, and where it places on the leaderboard
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct NewHighScore {
+    pub mode: GameMode,
+    pub entry: LeaderboardEntry,
+    /// Index on the leaderboard, or `None` if the run didn't make it
+    pub rank: Option<usize>,
+    /// Whether a name has been entered and the run added to the leaderboard
+    pub saved: bool,
+}
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct GameOverMenu;
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct SaveScoreButton;
+
+fn setup_new_high_score(
+    mut high_score: ResMut<NewHighScore>,
+    score: Res<PlayerScore>,
+    timer: Res<GameTimer>,
+    player_count: Res<PlayerCount>,
+    leaderboard: Res<Leaderboard>,
+) {
+    let mode = GameMode::from_player_count(player_count.0);
+    let entry = LeaderboardEntry::new("", &score, &timer);
+    *high_score = NewHighScore {
+        mode,
+        rank: leaderboard.rank_for(mode, entry.score),
+        entry,
+        saved: false,
+    };
+}
+
+fn setup_menu(
+    mut commands: Commands,
+    score: Res<PlayerScore>,
+    timer: Res<GameTimer>,
+    high_score: Res<NewHighScore>,
+    leaderboard: Res<Leaderboard>,
+    rng: Res<GameRng>,
+) {
+    spawn_menu(
         &mut commands,
-        StateScoped(AppState::GameOver),
+        &score,
+        &timer,
+        &high_score,
+        &leaderboard,
+        rng.seed(),
+    );
+}
+
+fn spawn_menu(
+    commands: &mut Commands,
+    score: &PlayerScore,
+    timer: &GameTimer,
+    high_score: &NewHighScore,
+    leaderboard: &Leaderboard,
+    seed: u64,
+) {
+    root(
+        commands,
+        (GameOverMenu, StateScoped(AppState::GameOver)),
         (c_full_screen, c_center, c_col),
     )
     .with_children(|p| {
@@ -31,12 +101,98 @@ fn setup_menu(mut commands: Commands, score: Res<PlayerScore>, timer: Res<GameTi
                 css::RED,
             );
             stats_table_row(p, "Level", format!("{}", score.level), css::GOLD);
+            stats_table_row(
+                p,
+                "Gold Earned",
+                format!("{}", gold_for_run(score, timer)),
+                css::ORANGE,
+            );
+            stats_table_row(p, "Score", format!("{}", high_score.entry.score), css::LIME);
+            stats_table_row(p, "Seed", format!("{}", seed), css::SILVER);
         });
+        if let Some(rank) = high_score.rank {
+            if high_score.saved {
+                leaderboard_table(p, leaderboard.entries(high_score.mode), Some(rank));
+            } else {
+                node(p, (), c_margin_bottom(MENU_SPACER)).with_children(|p| {
+                    text(
+                        p,
+                        format!(
+                            "New {} high score, rank #{}! Enter your name:",
+                            high_score.mode,
+                            rank + 1
+                        ),
+                        (),
+                        TextStyle::default(),
+                    );
+                });
+                text_input_widget(p, "", constants::LEADERBOARD_NAME_LENGTH, ());
+                menu_button_widget(p, "Save Score", SaveScoreButton);
+            }
+        }
         menu_button_widget(p, "Retry", MenuButtonAction::Play);
         menu_button_widget(p, "Quit to Menu", MenuButtonAction::MainMenu);
     });
 }
 
+/// System that adds the run to the leaderboard once a name is entered, either by pressing enter
+/// in the name field or the save button, and shows the leaderboard with the new rank highlighted
+fn handle_high_score_submitted(
+    mut commands: Commands,
+    mut submitted_events: EventReader<TextInputSubmitted>,
+    button_query: Query<&Interaction, (With<SaveScoreButton>, Changed<Interaction>)>,
+    input_query: Query<&TextInput>,
+    menu_query: Query<Entity, With<GameOverMenu>>,
+    mut high_score: ResMut<NewHighScore>,
+    mut leaderboard: ResMut<Leaderboard>,
+    storage: Res<GameStorage>,
+    score: Res<PlayerScore>,
+    timer: Res<GameTimer>,
+    rng: Res<GameRng>,
+) {
+    let submitted = submitted_events
+        .read()
+        .filter(|event| input_query.contains(event.entity))
+        .last()
+        .map(|event| event.value.clone());
+    let pressed = button_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    let name = match submitted {
+        Some(name) => name,
+        None if pressed => input_query
+            .iter()
+            .next()
+            .map(|input| input.value.clone())
+            .unwrap_or_default(),
+        None => return,
+    };
+    if high_score.saved {
+        return;
+    }
+
+    let name = match name.trim() {
+        "" => constants::LEADERBOARD_DEFAULT_NAME.to_string(),
+        name => name.to_string(),
+    };
+    high_score.entry.name = name;
+    high_score.rank = leaderboard.insert(high_score.mode, high_score.entry.clone());
+    high_score.saved = true;
+    storage.save_json(Leaderboard::STORAGE_KEY, &*leaderboard);
+
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_menu(
+        &mut commands,
+        &score,
+        &timer,
+        &high_score,
+        &leaderboard,
+        rng.seed(),
+    );
+}
+
 fn stats_table(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
     node(
         parent,
@@ -0,0 +1,124 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_ui_helpers::*;
+
+use crate::{game_state::*, *};
+
+use super::{power_up_panel, MENU_SPACER, TABLE_SPACER};
+
+pub struct LeaderboardMenuPlugin;
+
+impl Plugin for LeaderboardMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Leaderboard), setup_menu);
+    }
+}
+
+fn setup_menu(mut commands: Commands, leaderboard: Res<Leaderboard>) {
+    root(
+        &mut commands,
+        StateScoped(AppState::Leaderboard),
+        (c_full_screen, c_center, c_col),
+    )
+    .with_children(|p| {
+        power_up_panel(p, |p| {
+            menu_title(p, "Leaderboard");
+            // one column for each game mode
+            node(
+                p,
+                (),
+                (c_row, c_w_100, |b: &mut NodeBundle| {
+                    b.style.column_gap = Val::Px(MENU_SPACER * 2.);
+                }),
+            )
+            .with_children(|p| {
+                for mode in GameMode::ALL {
+                    node(p, (), (c_col, c_flex_grow, c_align_center)).with_children(|p| {
+                        node(p, (), c_margin_bottom(MENU_SPACER)).with_children(|p| {
+                            text(
+                                p,
+                                format!("{}", mode),
+                                (),
+                                TextStyle {
+                                    font_size: 28.,
+                                    ..default()
+                                },
+                            );
+                        });
+                        leaderboard_table(p, leaderboard.entries(mode), None);
+                    });
+                }
+            });
+        });
+        menu_button_widget(p, "Back", MenuButtonAction::MainMenu);
+    });
+}
+
+/// Table of leaderboard entries, with the entry at `highlight` picked out
+pub(super) fn leaderboard_table(
+    parent: &mut ChildBuilder,
+    entries: &[LeaderboardEntry],
+    highlight: Option<usize>,
+) {
+    if entries.is_empty() {
+        node(parent, (), c_margin_bottom(MENU_SPACER)).with_children(|p| {
+            text(
+                p,
+                "No runs yet",
+                (),
+                TextStyle {
+                    color: css::GRAY.into(),
+                    ..default()
+                },
+            );
+        });
+        return;
+    }
+
+    node(
+        parent,
+        (),
+        (
+            c_w_100,
+            c_margin_bottom(MENU_SPACER),
+            |b: &mut NodeBundle| {
+                b.style.display = Display::Grid;
+                b.style.grid_template_columns = vec![
+                    RepeatedGridTrack::fr(1, 1.),
+                    RepeatedGridTrack::fr(1, 3.),
+                    RepeatedGridTrack::fr(1, 2.),
+                    RepeatedGridTrack::fr(1, 2.),
+                ];
+            },
+        ),
+    )
+    .with_children(|p| {
+        for header in ["#", "Name", "Score", "Time"] {
+            leaderboard_cell(p, header, css::GOLD);
+        }
+        for (index, entry) in entries.iter().enumerate() {
+            let color = if highlight == Some(index) {
+                css::LIME
+            } else {
+                css::WHITE
+            };
+            leaderboard_cell(p, format!("{}", index + 1), color);
+            leaderboard_cell(p, entry.name.clone(), color);
+            leaderboard_cell(p, format!("{}", entry.score), color);
+            leaderboard_cell(p, format_time(entry.seconds), color);
+        }
+    });
+}
+
+fn leaderboard_cell(parent: &mut ChildBuilder, value: impl Into<String>, color: Srgba) {
+    node(parent, (), c_padding(TABLE_SPACER)).with_children(|p| {
+        text(
+            p,
+            value,
+            (),
+            TextStyle {
+                color: color.into(),
+                ..default()
+            },
+        );
+    });
+}
@@ -7,12 +7,11 @@ pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Menu), setup_menu)
-            .add_systems(Update, handle_input.run_if(in_state(AppState::Menu)));
+        app.add_systems(OnEnter(AppState::Menu), setup_menu);
     }
 }
 
-fn setup_menu(mut commands: Commands) {
+fn setup_menu(mut commands: Commands, storage: Res<GameStorage>, chosen_seed: Res<ChosenSeed>) {
     root(
         &mut commands,
         StateScoped(AppState::Menu),
@@ -20,13 +19,16 @@ fn setup_menu(mut commands: Commands) {
     )
     .with_children(|p| {
         menu_title(p, constants::APP_NAME);
-        menu_button_widget(p, "Play", MenuButtonAction::Play);
+        if has_saved_run(&storage) {
+            menu_button_widget(p, "Continue", MenuButtonAction::Continue);
+        }
+        menu_button_widget(p, "Play", MenuButtonAction::SelectCharacter(1));
+        menu_button_widget(p, "Co-op", MenuButtonAction::SelectCharacter(2));
+        menu_button_widget(p, "Shop", MenuButtonAction::Shop);
+        menu_button_widget(p, "Leaderboard", MenuButtonAction::Leaderboard);
+        menu_button_widget(p, "Achievements", MenuButtonAction::Achievements);
+        menu_button_widget(p, "Settings", MenuButtonAction::Settings);
+        menu_button_widget(p, format!("Seed: {}", *chosen_seed), MenuButtonAction::Seed);
         menu_button_widget(p, "Quit", MenuButtonAction::Quit);
     });
 }
-
-fn handle_input(mut next_game_state: ResMut<NextState<AppState>>, keys: Res<ButtonInput<KeyCode>>) {
-    if keys.just_pressed(KeyCode::Enter) {
-        next_game_state.set(AppState::Game);
-    }
-}
@@ -27,24 +27,19 @@ fn setup_menu(mut commands: Commands) {
     .with_children(|p| {
         menu_title(p, "Paused");
         menu_button_widget(p, "Resume", MenuButtonAction::Resume);
-        menu_button_widget(p, "Quit", MenuButtonAction::Quit);
+        menu_button_widget(p, "Settings", MenuButtonAction::Settings);
+        menu_button_widget(p, "Save & Quit", MenuButtonAction::SaveAndQuit);
     });
 }
 
-fn handle_menu_input(
-    mut next_state: ResMut<NextState<GameState>>,
-    keys: Res<ButtonInput<KeyCode>>,
-) {
-    if keys.just_pressed(KeyCode::Escape) {
+fn handle_menu_input(mut next_state: ResMut<NextState<GameState>>, actions: Res<ActionState>) {
+    if actions.just_pressed(InputAction::Pause) {
         next_state.set(GameState::Play);
     }
 }
 
-fn handle_game_input(
-    mut next_state: ResMut<NextState<GameState>>,
-    keys: Res<ButtonInput<KeyCode>>,
-) {
-    if keys.just_pressed(KeyCode::Escape) {
+fn handle_game_input(mut next_state: ResMut<NextState<GameState>>, actions: Res<ActionState>) {
+    if actions.just_pressed(InputAction::Pause) {
         next_state.set(GameState::Paused);
     }
 }
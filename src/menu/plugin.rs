@@ -7,20 +7,29 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            AchievementsMenuPlugin,
             MainMenuPlugin,
             GameOverMenuPlugin,
+            LeaderboardMenuPlugin,
             PauseMenuPlugin,
+            SettingsMenuPlugin,
+            SeedMenuPlugin,
             PowerUpMenuPlugin,
+            CharacterSelectMenuPlugin,
+            ShopMenuPlugin,
         ))
         .add_systems(Update, handle_button_click);
     }
 }
 
 fn handle_button_click(
+    mut commands: Commands,
     query: Query<(&MenuButtonAction, &Interaction), Changed<Interaction>>,
     mut app_exit_writer: EventWriter<AppExit>,
     mut next_app_state: ResMut<NextState<AppState>>,
     mut next_game_state: ResMut<NextState<GameState>>,
+    mut player_count: ResMut<PlayerCount>,
+    app_state: Res<State<AppState>>,
 ) {
     for (action, interaction) in query.iter() {
         if *interaction == Interaction::Pressed {
@@ -28,9 +37,43 @@ fn handle_button_click(
                 MenuButtonAction::Play => {
                     next_app_state.set(AppState::Game);
                 }
+                MenuButtonAction::Continue => {
+                    commands.add(continue_saved_run);
+                }
+                MenuButtonAction::SelectCharacter(count) => {
+                    player_count.0 = count;
+                    next_app_state.set(AppState::CharacterSelect);
+                }
+                MenuButtonAction::Shop => {
+                    next_app_state.set(AppState::Shop);
+                }
+                MenuButtonAction::Achievements => {
+                    next_app_state.set(AppState::Achievements);
+                }
+                MenuButtonAction::Leaderboard => {
+                    next_app_state.set(AppState::Leaderboard);
+                }
+                MenuButtonAction::Settings => {
+                    if *app_state.get() == AppState::Game {
+                        next_game_state.set(GameState::Settings);
+                    } else {
+                        next_app_state.set(AppState::Settings);
+                    }
+                }
+                MenuButtonAction::Pause => {
+                    next_game_state.set(GameState::Paused);
+                }
+                MenuButtonAction::Seed => {
+                    next_app_state.set(AppState::Seed);
+                }
                 MenuButtonAction::Quit => {
                     app_exit_writer.send(AppExit::Success);
                 }
+                MenuButtonAction::SaveAndQuit => {
+                    commands.add(save_run);
+                    next_app_state.set(AppState::Menu);
+                    next_game_state.set(GameState::Play);
+                }
                 MenuButtonAction::MainMenu => {
                     next_app_state.set(AppState::Menu);
                 }
@@ -3,52 +3,129 @@ use bevy_ui_helpers::*;
 
 use crate::{game_state::*, *};
 
-use super::{MENU_SPACER, TABLE_SPACER};
+use super::{CARD_SPACER, MENU_SPACER, TABLE_SPACER};
 
 pub struct PowerUpMenuPlugin;
 
 impl Plugin for PowerUpMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::PowerUp), setup_menu)
-            .add_systems(
-                Update,
-                handle_power_up_button_clicked.run_if(in_state(GameState::PowerUp)),
-            );
+        app.add_systems(
+            Update,
+            (
+                handle_power_up_button_clicked,
+                handle_evolution_button_clicked,
+                handle_item_button_clicked,
+                show_next_power_up_menu,
+            )
+                .chain()
+                .run_if(in_state(GameState::PowerUp)),
+        );
     }
 }
 
-const POWER_UP_FONT_SIZE_NAME: f32 = 24.;
-const POWER_UP_FONT_SIZE_DESCRIPTION: f32 = 18.;
-const POWER_UP_SPACER: f32 = 30.;
 const POWER_UP_SPACER_LG: f32 = 60.;
 
+/// Root of the power up menu of the player at the front of [`PendingPowerUps`]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct PowerUpMenu {
+    /// Whether the next power up chosen is banished instead of taken
+    pub banishing: bool,
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub enum PowerUpButton {
+    /// Takes the power up, or banishes its stat while banishing
+    PowerUp(PowerUp),
+    /// Draws new power ups
+    Reroll,
+    /// Takes a small heal instead of a power up
+    Skip,
+    /// Toggles banishing
+    Banish,
+}
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct PowerUpDescriptionLabel;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct EvolutionButton(pub Evolution);
+
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component, Debug)]
-pub struct PowerUpButton(pub PowerUp);
+pub struct ItemButton(pub Item);
+
+/// System that shows the menu for the next player that has to pick a power up, and resumes the
+/// game once every player has picked
+fn show_next_power_up_menu(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut pending: ResMut<PendingPowerUps>,
+    menu_query: Query<(), With<PowerUpMenu>>,
+    player_query: Query<(&Player, &PlayerStats)>,
+    mut rng: ResMut<GameRng>,
+) {
+    if !menu_query.is_empty() {
+        return;
+    }
+
+    while let Some(next) = pending.front() {
+        if let Ok((player, stats)) = player_query.get(next.player) {
+            setup_menu(&mut commands, next, player, stats, &mut rng);
+            return;
+        }
+        // the player went down before getting to pick
+        pending.queue.pop_front();
+    }
+    next_state.set(GameState::Play);
+}
+
+fn setup_menu(
+    commands: &mut Commands,
+    next: &PendingPowerUp,
+    player: &Player,
+    stats: &PlayerStats,
+    rng: &mut GameRng,
+) {
+    // Offer the first available weapon evolution, and sometimes an item, in place of stat upgrades
+    let evolution = stats.get_available_evolutions().first().copied();
+    let item = Item::new_random_offer(stats, rng.stream(RngStream::Loot));
 
-fn setup_menu(mut commands: Commands, score: Res<PlayerScore>, stats: Res<PlayerStats>) {
-    // Generate 3 random stat upgrades
-    let power_ups = [
-        PowerUp::new_random(),
-        PowerUp::new_random(),
-        PowerUp::new_random(),
-    ];
+    // Generate random stat upgrades for the remaining cards, each for a different stat and skipping
+    // weapons at max level
+    let power_up_count = 3 - evolution.iter().count() - item.iter().count();
+    let power_ups =
+        PowerUp::new_random_offers(stats, power_up_count, rng.stream(RngStream::PowerUps));
 
     root(
-        &mut commands,
-        StateScoped(GameState::PowerUp),
+        commands,
+        (PowerUpMenu::default(), StateScoped(GameState::PowerUp)),
         (c_full_screen, c_center, c_col),
     )
     .with_children(|p| {
-        stats_panel(p, &stats);
+        stats_panel(p, stats);
         power_up_panel(p, |p| {
-            menu_title(p, "Choose Power Up");
-            power_up_description(p, format!("You have reached level {}!", score.level));
+            menu_title(p, format_title(next));
+            power_up_description(
+                p,
+                format_level_up_description(player, next),
+                PowerUpDescriptionLabel,
+            );
             power_up_layout(p, |p| {
+                if let Some(evolution) = evolution {
+                    evolution_widget(p, evolution);
+                }
+                if let Some(item) = item {
+                    item_widget(p, item);
+                }
                 for power_up in power_ups {
                     power_up_widget(p, power_up);
                 }
             });
+            power_up_options(p, stats);
         });
     });
 }
@@ -93,6 +170,8 @@ fn stats_panel(parent: &mut ChildBuilder, stats: &PlayerStats) {
                 stats_table_row(p, " ", " ");
                 // Movement
                 stats_table_row(p, "Move Speed", format!("{}%", stats.move_speed));
+                stats_table_row(p, "Dash Charges", format!("{}", stats.dash_charges));
+                stats_table_row(p, "Dash Cooldown", format!("{}", stats.dash_cooldown));
                 stats_table_row(p, " ", " ");
                 // Attack
                 stats_table_row(p, "Damage", format!("{}%", stats.damage_percent));
@@ -114,15 +193,45 @@ fn stats_panel(parent: &mut ChildBuilder, stats: &PlayerStats) {
                     "Orbs",
                     format!("{}", constants::ORB_BASE_AMOUNT + stats.extra_orbs),
                 );
+                stats_table_row(p, "Orb Rings", format!("{}", stats.orb_rings));
+                stats_table_row(p, "Orb Radius", format!("{}", stats.orb_orbit_radius));
+                stats_table_row(p, "Orb Speed", format!("{}", stats.orb_speed));
+                stats_table_row(p, "Orb Direction", format!("{}", stats.orb_direction));
+                stats_table_row(
+                    p,
+                    "Orbital Projectiles",
+                    format!("{}", stats.orbital_projectiles),
+                );
+                stats_table_row(p, "Orbital Laps", format!("{}", stats.orbital_max_laps));
+                stats_table_row(p, "Laser Beams", format!("{}", stats.laser_beams));
+                stats_table_row(p, "Drones", format!("{}", stats.drones));
+                stats_table_row(p, "Turrets", format!("{}", stats.turrets));
                 stats_table_row(p, " ", " ");
                 // Pickup Radius
                 stats_table_row(p, "Pickup Radius", format!("{}", stats.pickup_radius));
+                stats_table_row(p, "Luck", format!("{}%", stats.luck));
+                stats_table_row(p, "Rerolls", format!("{}", stats.rerolls));
+                stats_table_row(p, "Skips", format!("{}", stats.skips));
+                stats_table_row(p, "Banishes", format!("{}", stats.banishes));
+                // Evolutions
+                for evolution in stats.evolutions.iter() {
+                    stats_table_row(p, "Evolution", format!("{}", evolution));
+                }
+                // Items
+                stats_table_row(
+                    p,
+                    "Item Slots",
+                    format!("{} / {}", stats.items.len(), constants::PLAYER_ITEM_SLOTS),
+                );
+                for item in stats.items.iter() {
+                    stats_table_row(p, "Item", format!("{}", item));
+                }
             });
         });
     });
 }
 
-fn power_up_panel(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+pub(super) fn power_up_panel(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
     node(
         parent,
         (),
@@ -139,7 +248,7 @@ fn power_up_panel(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBui
     .with_children(children);
 }
 
-fn power_up_layout(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+pub(super) fn power_up_layout(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
     node(
         parent,
         (),
@@ -158,95 +267,216 @@ fn power_up_layout(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBu
     .with_children(children);
 }
 
-fn power_up_description(parent: &mut ChildBuilder, value: impl Into<String>) {
-    node(parent, (), c_margin_bottom(POWER_UP_SPACER)).with_children(|p| {
-        text(p, value, (), TextStyle::default());
+fn power_up_description(parent: &mut ChildBuilder, value: impl Into<String>, extras: impl Bundle) {
+    node(parent, (), c_margin_bottom(CARD_SPACER)).with_children(|p| {
+        text(p, value, extras, TextStyle::default());
     });
 }
 
-fn power_up_widget(parent: &mut ChildBuilder, power_up: PowerUp) {
+/// Reroll, skip and banish buttons, showing how many uses are left
+fn power_up_options(parent: &mut ChildBuilder, stats: &PlayerStats) {
     node(
         parent,
-        Name::new(format!("Power Up: {:?}", power_up.stat)),
-        (
-            c_col,
-            c_border_white,
-            c_background_color(css::SLATE_GRAY),
-            c_align_center,
-            c_padding(MENU_SPACER),
-            c_border_radius(10.),
-        ),
+        (),
+        (c_row, c_margin_top(CARD_SPACER), |b: &mut NodeBundle| {
+            b.style.column_gap = Val::Px(MENU_SPACER);
+        }),
     )
     .with_children(|p| {
-        node(
-            p,
-            (),
-            (
-                c_w_100,
-                c_center,
-                c_margin_bottom(POWER_UP_SPACER),
-                c_overflow_clip_x,
-            ),
-        )
-        .with_children(|p| {
-            text_centered(
-                p,
-                format!("{}", power_up.stat),
-                (),
-                TextStyle {
-                    font_size: POWER_UP_FONT_SIZE_NAME,
-                    ..default()
-                },
-            );
-        });
-        node(
-            p,
-            (),
-            (c_col, c_margin_bottom(POWER_UP_SPACER), c_overflow_clip_x),
-        )
-        .with_children(|p| {
-            text_centered(
-                p,
-                power_up.description(),
-                (),
-                TextStyle {
-                    font_size: POWER_UP_FONT_SIZE_DESCRIPTION,
-                    ..default()
-                },
-            );
-        });
-        node(
-            p,
-            (),
-            (c_col_w_100, c_overflow_clip_x, |b: &mut NodeBundle| {
-                b.style.margin.top = Val::Auto;
-            }),
-        )
-        .with_children(|p| {
+        let options = [
+            ("Reroll", stats.rerolls, PowerUpButton::Reroll),
+            ("Skip", stats.skips, PowerUpButton::Skip),
+            ("Banish", stats.banishes, PowerUpButton::Banish),
+        ];
+        for (title, uses, button) in options {
             button_widget(
                 p,
-                "Choose",
-                PowerUpButton(power_up.clone()),
+                format!("{} ({})", title, uses),
+                button,
                 |b: &mut ButtonBundle| {
                     b.style.padding = UiRect::all(Val::Px(MENU_SPACER));
-                    b.style.width = Val::Percent(100.);
                 },
-            )
-        });
+            );
+        }
     });
 }
 
+/// Shows which of the player's picks is being made when several levels were gained at once
+fn format_title(next: &PendingPowerUp) -> String {
+    if next.picks > 1 {
+        format!("Choose Power Up ({} of {})", next.pick, next.picks)
+    } else {
+        "Choose Power Up".to_string()
+    }
+}
+
+fn format_level_up_description(player: &Player, next: &PendingPowerUp) -> String {
+    format!(
+        "Player {} has reached level {}!",
+        player.index + 1,
+        next.level
+    )
+}
+
+fn power_up_widget(parent: &mut ChildBuilder, power_up: PowerUp) {
+    choice_card_widget(
+        parent,
+        Name::new(format!("Power Up: {:?}", power_up.stat)),
+        format!("{}", power_up.stat),
+        format!("{}\n\n{}", power_up.rarity, power_up.description()),
+        "Choose",
+        power_up.rarity.color(),
+        PowerUpButton::PowerUp(power_up.clone()),
+    );
+}
+
+fn evolution_widget(parent: &mut ChildBuilder, evolution: Evolution) {
+    choice_card_widget(
+        parent,
+        Name::new(format!("Evolution: {:?}", evolution)),
+        format!("{}", evolution),
+        evolution.description(),
+        "Choose",
+        css::DARK_ORANGE,
+        EvolutionButton(evolution),
+    );
+}
+
+fn item_widget(parent: &mut ChildBuilder, item: Item) {
+    choice_card_widget(
+        parent,
+        Name::new(format!("Item: {:?}", item)),
+        format!("{}", item),
+        format!("Item\n\n{}", item.description()),
+        "Choose",
+        css::TEAL,
+        ItemButton(item),
+    );
+}
+
 fn handle_power_up_button_clicked(
+    mut commands: Commands,
     query: Query<(&PowerUpButton, &Interaction), Changed<Interaction>>,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut stats: ResMut<PlayerStats>,
+    mut menu_query: Query<(Entity, &mut PowerUpMenu)>,
+    mut label_query: Query<&mut Text, With<PowerUpDescriptionLabel>>,
+    mut pending: ResMut<PendingPowerUps>,
+    mut player_query: Query<(&Player, &mut PlayerStats, &mut Health)>,
+    mut history: ResMut<RunHistory>,
+) {
+    for (button, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(next) = pending.front().cloned() else {
+            continue;
+        };
+        let Ok((menu_entity, mut menu)) = menu_query.get_single_mut() else {
+            continue;
+        };
+        let Ok((player, mut stats, mut health)) = player_query.get_mut(next.player) else {
+            continue;
+        };
+
+        match button {
+            PowerUpButton::PowerUp(power_up) => {
+                if menu.banishing {
+                    // offer new power ups to the same player, without the banished stat
+                    stats.banish(power_up.stat);
+                } else {
+                    stats.add_power_up(power_up);
+                    history.power_ups.push(ChosenPowerUp {
+                        player: player.index,
+                        level: next.level,
+                        choice: PowerUpChoice::PowerUp(power_up.clone()),
+                    });
+                    pending.queue.pop_front();
+                }
+                commands.entity(menu_entity).despawn_recursive();
+            }
+            PowerUpButton::Reroll => {
+                if stats.use_reroll() {
+                    commands.entity(menu_entity).despawn_recursive();
+                }
+            }
+            PowerUpButton::Skip => {
+                if stats.use_skip() {
+                    let amount = health.max_health * constants::SKIP_HEAL_PERCENT / 100.;
+                    health.heal(amount);
+                    pending.queue.pop_front();
+                    commands.entity(menu_entity).despawn_recursive();
+                }
+            }
+            PowerUpButton::Banish => {
+                if stats.banishes > 0 {
+                    menu.banishing = !menu.banishing;
+                    for mut text in label_query.iter_mut() {
+                        text.sections[0].value = if menu.banishing {
+                            "Choose a power up to banish".to_string()
+                        } else {
+                            format_level_up_description(player, &next)
+                        };
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn handle_evolution_button_clicked(
+    mut commands: Commands,
+    query: Query<(&EvolutionButton, &Interaction), Changed<Interaction>>,
+    menu_query: Query<Entity, With<PowerUpMenu>>,
+    mut pending: ResMut<PendingPowerUps>,
+    mut player_query: Query<(&Player, &mut PlayerStats)>,
+    mut history: ResMut<RunHistory>,
 ) {
     for (button, interaction) in query.iter() {
         if *interaction != Interaction::Pressed {
             continue;
         }
-        stats.add_power_up(&button.0);
-        next_state.set(GameState::Play);
+        let Some(next) = pending.queue.pop_front() else {
+            continue;
+        };
+        if let Ok((player, mut stats)) = player_query.get_mut(next.player) {
+            stats.add_evolution(button.0);
+            history.power_ups.push(ChosenPowerUp {
+                player: player.index,
+                level: next.level,
+                choice: PowerUpChoice::Evolution(button.0),
+            });
+        }
+        for menu_entity in menu_query.iter() {
+            commands.entity(menu_entity).despawn_recursive();
+        }
+    }
+}
+
+fn handle_item_button_clicked(
+    mut commands: Commands,
+    query: Query<(&ItemButton, &Interaction), Changed<Interaction>>,
+    menu_query: Query<Entity, With<PowerUpMenu>>,
+    mut pending: ResMut<PendingPowerUps>,
+    mut player_query: Query<(&Player, &mut PlayerStats)>,
+    mut history: ResMut<RunHistory>,
+) {
+    for (button, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(next) = pending.queue.pop_front() else {
+            continue;
+        };
+        if let Ok((player, mut stats)) = player_query.get_mut(next.player) {
+            stats.add_item(button.0);
+            history.power_ups.push(ChosenPowerUp {
+                player: player.index,
+                level: next.level,
+                choice: PowerUpChoice::Item(button.0),
+            });
+        }
+        for menu_entity in menu_query.iter() {
+            commands.entity(menu_entity).despawn_recursive();
+        }
     }
 }
 
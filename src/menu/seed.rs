@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy_ui_helpers::*;
+
+use crate::{game_state::*, *};
+
+use super::{power_up_panel, MENU_SPACER};
+
+pub struct SeedMenuPlugin;
+
+impl Plugin for SeedMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Seed), setup_menu)
+            .add_systems(
+                Update,
+                handle_seed_submitted.run_if(in_state(AppState::Seed)),
+            );
+    }
+}
+
+fn setup_menu(mut commands: Commands, chosen_seed: Res<ChosenSeed>) {
+    let value = chosen_seed
+        .0
+        .map(|seed| seed.to_string())
+        .unwrap_or_default();
+    root(
+        &mut commands,
+        StateScoped(AppState::Seed),
+        (c_full_screen, c_center, c_col),
+    )
+    .with_children(|p| {
+        power_up_panel(p, |p| {
+            menu_title(p, "Seed");
+            node(p, (), c_margin_bottom(MENU_SPACER)).with_children(|p| {
+                text(
+                    p,
+                    "Type a number or any text to replay the same run, or leave it empty for a \
+                     random run. Press enter to confirm.",
+                    (),
+                    TextStyle::default(),
+                );
+            });
+            text_input_widget(p, value, constants::SEED_INPUT_LENGTH, ());
+        });
+        menu_button_widget(p, "Back", MenuButtonAction::MainMenu);
+    });
+}
+
+/// System that sets the seed of the next runs once it is entered, and goes back to the main menu
+fn handle_seed_submitted(
+    mut submitted_events: EventReader<TextInputSubmitted>,
+    mut chosen_seed: ResMut<ChosenSeed>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    if let Some(event) = submitted_events.read().last() {
+        *chosen_seed = ChosenSeed::parse(&event.value);
+        next_state.set(AppState::Menu);
+    }
+}
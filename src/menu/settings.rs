@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use bevy_ui_helpers::*;
+
+use crate::{game_state::*, *};
+
+use super::power_up_panel;
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        let in_settings = in_state(AppState::Settings).or_else(in_state(GameState::Settings));
+        app.init_resource::<SettingsPage>()
+            .add_systems(OnEnter(AppState::Settings), setup_menu)
+            .add_systems(OnEnter(GameState::Settings), setup_menu)
+            .add_systems(OnExit(AppState::Settings), stop_rebinding)
+            .add_systems(OnExit(GameState::Settings), stop_rebinding)
+            .add_systems(
+                Update,
+                (
+                    (
+                        handle_settings_button_clicked,
+                        rebuild_menu.run_if(
+                            resource_changed::<Settings>
+                                .or_else(resource_changed::<InputBindings>)
+                                .or_else(resource_changed::<Rebinding>)
+                                .or_else(resource_changed::<SettingsPage>),
+                        ),
+                    )
+                        .chain()
+                        .run_if(in_settings),
+                    handle_menu_input.run_if(in_state(GameState::Settings)),
+                ),
+            );
+    }
+}
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct SettingsMenu;
+
+/// The page of the settings screen being shown
+#[derive(Resource, Default, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
+#[reflect(Resource, Default, Debug, PartialEq)]
+pub enum SettingsPage {
+    #[default]
+    General,
+    Controls,
+}
+
+#[derive(Component, Debug, Copy, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub enum SettingsButton {
+    /// Moves a setting to its next value
+    Setting(SettingOption),
+    /// Waits for a new input to bind to an action
+    Binding(InputAction),
+    ResetBindings,
+    Page(SettingsPage),
+}
+
+fn setup_menu(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    mut page: ResMut<SettingsPage>,
+    app_state: Res<State<AppState>>,
+) {
+    *page = SettingsPage::General;
+    spawn_settings_menu(
+        &mut commands,
+        &settings,
+        &bindings,
+        &rebinding,
+        *page,
+        *app_state.get(),
+        None,
+    );
+}
+
+fn stop_rebinding(mut rebinding: ResMut<Rebinding>) {
+    *rebinding = Rebinding::default();
+}
+
+/// Spawns the settings screen, focusing `focus` if given. In game it is shown over the pause menu
+/// and goes back to it, otherwise it goes back to the main menu.
+fn spawn_settings_menu(
+    commands: &mut Commands,
+    settings: &Settings,
+    bindings: &InputBindings,
+    rebinding: &Rebinding,
+    page: SettingsPage,
+    app_state: AppState,
+    focus: Option<SettingsButton>,
+) {
+    let in_game = app_state == AppState::Game;
+    let mut root = if in_game {
+        root(
+            commands,
+            (SettingsMenu, StateScoped(GameState::Settings)),
+            (c_full_screen, c_center, c_col),
+        )
+    } else {
+        root(
+            commands,
+            (SettingsMenu, StateScoped(AppState::Settings)),
+            (c_full_screen, c_center, c_col),
+        )
+    };
+    root.with_children(|p| {
+        power_up_panel(p, |p| match page {
+            SettingsPage::General => {
+                menu_title(p, "Settings");
+                node(p, (), (c_col, c_align_center)).with_children(|p| {
+                    for option in SettingOption::ALL {
+                        let title = format!("{}: {}", option, option.value_label(settings));
+                        settings_button(p, title, SettingsButton::Setting(option), focus);
+                    }
+                    let controls = SettingsButton::Page(SettingsPage::Controls);
+                    settings_button(p, "Controls", controls, focus);
+                });
+            }
+            SettingsPage::Controls => {
+                menu_title(p, "Controls");
+                node(p, (), (c_col, c_align_center)).with_children(|p| {
+                    for action in InputAction::ALL {
+                        let title = binding_label(action, bindings, rebinding);
+                        settings_button(p, title, SettingsButton::Binding(action), focus);
+                    }
+                    let reset = SettingsButton::ResetBindings;
+                    settings_button(p, "Reset Controls", reset, focus);
+                });
+            }
+        });
+        match page {
+            SettingsPage::General if in_game => {
+                menu_button_widget(p, "Back", MenuButtonAction::Pause);
+            }
+            SettingsPage::General => menu_button_widget(p, "Back", MenuButtonAction::MainMenu),
+            SettingsPage::Controls => {
+                let back = SettingsButton::Page(SettingsPage::General);
+                menu_button_widget(p, "Back", back);
+            }
+        }
+    });
+}
+
+fn settings_button(
+    parent: &mut ChildBuilder,
+    title: impl Into<String>,
+    button: SettingsButton,
+    focus: Option<SettingsButton>,
+) {
+    let class = |b: &mut ButtonBundle| {
+        b.style.width = Val::Px(360.);
+        b.style.height = Val::Px(45.);
+        b.style.margin = UiRect::bottom(Val::Px(8.));
+    };
+    if focus == Some(button) {
+        button_widget(parent, title, (button, Focused), class);
+    } else {
+        button_widget(parent, title, button, class);
+    }
+}
+
+/// Returns the title of an action's button, showing its first key and gamepad button
+fn binding_label(action: InputAction, bindings: &InputBindings, rebinding: &Rebinding) -> String {
+    if rebinding.action == Some(action) {
+        return match rebinding.rejected {
+            Some(key) => format!(
+                "{}: {} is player 2's, press another",
+                action,
+                InputBinding::Key(key)
+            ),
+            None => format!("{}: Press a key or button", action),
+        };
+    }
+    let first = |is_kind: fn(&InputBinding) -> bool| {
+        bindings
+            .get(action)
+            .iter()
+            .find(|binding| is_kind(binding))
+            .map(|binding| binding.to_string())
+    };
+    let inputs: Vec<String> = [
+        first(|binding| matches!(binding, InputBinding::Key(_))),
+        first(|binding| matches!(binding, InputBinding::Button(_))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if inputs.is_empty() {
+        format!("{}: None", action)
+    } else {
+        format!("{}: {}", action, inputs.join(" / "))
+    }
+}
+
+fn handle_settings_button_clicked(
+    query: Query<(&SettingsButton, &Interaction), Changed<Interaction>>,
+    storage: Res<GameStorage>,
+    registry: Res<AppTypeRegistry>,
+    mut settings: ResMut<Settings>,
+    mut bindings: ResMut<InputBindings>,
+    mut rebinding: ResMut<Rebinding>,
+    mut page: ResMut<SettingsPage>,
+) {
+    for (button, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match *button {
+            SettingsButton::Setting(option) => {
+                option.cycle(&mut settings);
+                storage.save_json(Settings::STORAGE_KEY, &*settings);
+            }
+            SettingsButton::Binding(action) => {
+                *rebinding = Rebinding {
+                    action: Some(action),
+                    ..default()
+                };
+            }
+            SettingsButton::ResetBindings => {
+                *bindings = InputBindings::default();
+                storage.save_reflect(InputBindings::STORAGE_KEY, &*bindings, &registry.read());
+            }
+            SettingsButton::Page(new_page) => *page = new_page,
+        }
+    }
+}
+
+/// System that rebuilds the menu to show new values, keeping focus on the button that was focused
+fn rebuild_menu(
+    mut commands: Commands,
+    menu_query: Query<Entity, With<SettingsMenu>>,
+    focus_query: Query<&SettingsButton, With<Focused>>,
+    settings: Res<Settings>,
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    page: Res<SettingsPage>,
+    app_state: Res<State<AppState>>,
+) {
+    let focus = match focus_query.iter().next().copied() {
+        // the page changed, so focus the first button, or the button that opens the page just left
+        Some(SettingsButton::Page(SettingsPage::Controls)) => None,
+        Some(SettingsButton::Page(SettingsPage::General)) => {
+            Some(SettingsButton::Page(SettingsPage::Controls))
+        }
+        focus => focus,
+    };
+
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_settings_menu(
+        &mut commands,
+        &settings,
+        &bindings,
+        &rebinding,
+        *page,
+        *app_state.get(),
+        focus,
+    );
+}
+
+fn handle_menu_input(mut next_state: ResMut<NextState<GameState>>, actions: Res<ActionState>) {
+    if actions.just_pressed(InputAction::Pause) {
+        next_state.set(GameState::Paused);
+    }
+}
@@ -0,0 +1,130 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_ui_helpers::*;
+
+use crate::{game_state::*, *};
+
+use super::{power_up_layout, power_up_panel, CARD_SPACER};
+
+pub struct ShopMenuPlugin;
+
+impl Plugin for ShopMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Shop), setup_menu)
+            .add_systems(
+                Update,
+                handle_shop_button_clicked.run_if(in_state(AppState::Shop)),
+            );
+    }
+}
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct ShopMenu;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct ShopButton(pub MetaUpgrade);
+
+fn setup_menu(mut commands: Commands, meta_progress: Res<MetaProgress>) {
+    spawn_shop_menu(&mut commands, &meta_progress, None);
+}
+
+/// Spawns the shop, focusing the button of `focus` if given
+fn spawn_shop_menu(
+    commands: &mut Commands,
+    meta_progress: &MetaProgress,
+    focus: Option<MetaUpgrade>,
+) {
+    root(
+        commands,
+        (ShopMenu, StateScoped(AppState::Shop)),
+        (c_full_screen, c_center, c_col),
+    )
+    .with_children(|p| {
+        power_up_panel(p, |p| {
+            menu_title(p, "Shop");
+            node(p, (), c_margin_bottom(CARD_SPACER)).with_children(|p| {
+                text(
+                    p,
+                    format!("Gold: {}", meta_progress.gold),
+                    (),
+                    TextStyle {
+                        color: css::ORANGE.into(),
+                        ..default()
+                    },
+                );
+            });
+            power_up_layout(p, |p| {
+                for upgrade in MetaUpgrade::ALL {
+                    upgrade_widget(p, meta_progress, upgrade, focus == Some(upgrade));
+                }
+            });
+        });
+        menu_button_widget(p, "Back", MenuButtonAction::MainMenu);
+    });
+}
+
+fn upgrade_widget(
+    parent: &mut ChildBuilder,
+    meta_progress: &MetaProgress,
+    upgrade: MetaUpgrade,
+    focused: bool,
+) {
+    let cost = meta_progress.cost(upgrade);
+    let (button_title, background) = match cost {
+        Some(cost) if cost <= meta_progress.gold => (format!("Buy: {} gold", cost), css::SEA_GREEN),
+        Some(cost) => (format!("Buy: {} gold", cost), css::DIM_GRAY),
+        None => ("Maxed".to_string(), css::DARK_GOLDENROD),
+    };
+    let description = format!(
+        "{} per level\n\nLevel {} / {}",
+        upgrade.power_up().description(),
+        meta_progress.level(upgrade),
+        upgrade.max_level()
+    );
+    let button = ShopButton(upgrade);
+    let name = Name::new(format!("Upgrade: {:?}", upgrade));
+    let title = format!("{}", upgrade);
+    if focused {
+        choice_card_widget(
+            parent,
+            name,
+            title,
+            description,
+            button_title,
+            background,
+            (button, Focused),
+        );
+    } else {
+        choice_card_widget(
+            parent,
+            name,
+            title,
+            description,
+            button_title,
+            background,
+            button,
+        );
+    }
+}
+
+fn handle_shop_button_clicked(
+    mut commands: Commands,
+    query: Query<(&ShopButton, &Interaction), Changed<Interaction>>,
+    menu_query: Query<Entity, With<ShopMenu>>,
+    storage: Res<GameStorage>,
+    mut meta_progress: ResMut<MetaProgress>,
+) {
+    for (button, interaction) in query.iter() {
+        if *interaction != Interaction::Pressed || !meta_progress.try_buy(button.0) {
+            continue;
+        }
+        storage.save_json(MetaProgress::STORAGE_KEY, &*meta_progress);
+
+        // rebuild the shop to show the new prices, keeping focus on the upgrade just bought
+        for entity in menu_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        spawn_shop_menu(&mut commands, &meta_progress, Some(button.0));
+    }
+}
@@ -1,2 +1,5 @@
 pub const MENU_SPACER: f32 = 10.;
 pub const TABLE_SPACER: f32 = 5.;
+pub const CARD_FONT_SIZE_NAME: f32 = 24.;
+pub const CARD_FONT_SIZE_DESCRIPTION: f32 = 18.;
+pub const CARD_SPACER: f32 = 30.;
@@ -4,7 +4,22 @@ use bevy::prelude::*;
 #[reflect(Component, Debug, PartialEq, Hash)]
 pub enum MenuButtonAction {
     Play,
+    /// Continues the run saved when quitting from the pause menu
+    Continue,
+    /// Picks characters for the given number of players
+    SelectCharacter(u32),
+    Shop,
+    Achievements,
+    Leaderboard,
+    /// Opens the settings screen, over the pause menu when in game
+    Settings,
+    /// Returns to the pause menu
+    Pause,
+    /// Opens the screen to choose the seed of the next runs
+    Seed,
     Quit,
+    /// Saves the run in progress and returns to the main menu
+    SaveAndQuit,
     MainMenu,
     Resume,
 }
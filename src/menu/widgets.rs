@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use bevy_ui_helpers::*;
 
+use crate::*;
+
+use super::{CARD_FONT_SIZE_DESCRIPTION, CARD_FONT_SIZE_NAME, CARD_SPACER, MENU_SPACER};
+
 pub fn menu_title(parent: &mut ChildBuilder, title: impl Into<String>) {
     node(parent, (), c_margin_bottom(10.)).with_children(|p| {
         text(
@@ -15,3 +19,79 @@ pub fn menu_title(parent: &mut ChildBuilder, title: impl Into<String>) {
         );
     });
 }
+
+/// A card with a title, description and a button, for picking one of several options
+pub fn choice_card_widget(
+    parent: &mut ChildBuilder,
+    name: Name,
+    title: String,
+    description: String,
+    button_title: impl Into<String>,
+    background: Srgba,
+    button_extras: impl Bundle,
+) {
+    node(
+        parent,
+        name,
+        (
+            c_col,
+            c_border_white,
+            c_background_color(background),
+            c_align_center,
+            c_padding(MENU_SPACER),
+            c_border_radius(10.),
+        ),
+    )
+    .with_children(|p| {
+        node(
+            p,
+            (),
+            (
+                c_w_100,
+                c_center,
+                c_margin_bottom(CARD_SPACER),
+                c_overflow_clip_x,
+            ),
+        )
+        .with_children(|p| {
+            text_centered(
+                p,
+                title,
+                (),
+                TextStyle {
+                    font_size: CARD_FONT_SIZE_NAME,
+                    ..default()
+                },
+            );
+        });
+        node(
+            p,
+            (),
+            (c_col, c_margin_bottom(CARD_SPACER), c_overflow_clip_x),
+        )
+        .with_children(|p| {
+            text_centered(
+                p,
+                description,
+                (),
+                TextStyle {
+                    font_size: CARD_FONT_SIZE_DESCRIPTION,
+                    ..default()
+                },
+            );
+        });
+        node(
+            p,
+            (),
+            (c_col_w_100, c_overflow_clip_x, |b: &mut NodeBundle| {
+                b.style.margin.top = Val::Auto;
+            }),
+        )
+        .with_children(|p| {
+            button_widget(p, button_title, button_extras, |b: &mut ButtonBundle| {
+                b.style.padding = UiRect::all(Val::Px(MENU_SPACER));
+                b.style.width = Val::Percent(100.);
+            })
+        });
+    });
+}
@@ -13,16 +13,27 @@ pub struct OrbPlugin;
 
 impl Plugin for OrbPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<OrbResources>().add_systems(
-            Update,
-            (
-                setup_new_orbs,
-                handle_collision_events.run_if(on_event::<CollisionEvent>()),
-                (tick_orb_hits, update_orb_transform).run_if(not_paused),
-                update_orbs_on_stats_change.run_if(resource_exists_and_changed::<PlayerStats>),
-            )
-                .run_if(in_game),
-        );
+        app.init_resource::<OrbResources>()
+            .add_systems(OnEnter(AppState::Game), reset_ring_of_fire_meshes)
+            .add_systems(
+                Update,
+                (
+                    (setup_new_orbs, setup_new_rings_of_fire),
+                    handle_collision_events.run_if(on_event::<CollisionEvent>()),
+                    (
+                        tick_orb_hits,
+                        update_orb_transform,
+                        update_ring_of_fire_transform,
+                        tick_rings_of_fire,
+                    )
+                        .run_if(not_paused),
+                    (
+                        update_orbs_on_stats_change,
+                        update_rings_of_fire_on_stats_change,
+                    ),
+                )
+                    .run_if(in_game),
+            );
     }
 }
 
@@ -31,15 +42,18 @@ impl Plugin for OrbPlugin {
 pub struct Orb {
     pub damage: f32,
     pub angle: f32,
+    /// Index of the ring this orb belongs to, starting from the innermost ring
+    pub ring: u32,
     pub hits: HashMap<Entity, Timer>,
 }
 
 impl Orb {
-    pub fn new(damage: f32, angle: f32) -> Self {
+    pub fn new(damage: f32, angle: f32, ring: u32) -> Self {
         assert!(damage >= 0.);
         Self {
             damage,
             angle,
+            ring,
             hits: HashMap::<Entity, Timer>::new(),
         }
     }
@@ -49,6 +63,7 @@ impl Orb {
 pub struct OrbBundle {
     pub name: Name,
     pub orb: Orb,
+    pub owned_by: OwnedBy,
     pub state_scoped: StateScoped<AppState>,
     pub transform: Transform,
     pub collider: Collider,
@@ -56,10 +71,11 @@ pub struct OrbBundle {
 }
 
 impl OrbBundle {
-    pub fn new(transform: Transform, damage: f32, angle: f32) -> Self {
+    pub fn new(owner: Entity, transform: Transform, damage: f32, angle: f32, ring: u32) -> Self {
         Self {
             name: Name::new("Orb"),
-            orb: Orb::new(damage, angle),
+            orb: Orb::new(damage, angle, ring),
+            owned_by: OwnedBy(owner),
             state_scoped: StateScoped(AppState::Game),
             transform,
             collider: Collider::Sphere(constants::ORB_RADIUS),
@@ -68,11 +84,61 @@ impl OrbBundle {
     }
 }
 
+/// A permanent ring of fire around the player. Replaces orbs once they evolve into
+/// [`Evolution::RingOfFire`], damaging every enemy touching the ring on each tick.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct RingOfFire {
+    pub damage: f32,
+    /// Index of the orb ring this replaces, starting from the innermost ring
+    pub ring: u32,
+    pub tick_timer: Timer,
+}
+
+impl RingOfFire {
+    pub fn new(damage: f32, ring: u32) -> Self {
+        assert!(damage >= 0.);
+        Self {
+            damage,
+            ring,
+            tick_timer: Timer::from_seconds(
+                constants::RING_OF_FIRE_TICK_SECS,
+                TimerMode::Repeating,
+            ),
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct RingOfFireBundle {
+    pub name: Name,
+    pub ring_of_fire: RingOfFire,
+    pub owned_by: OwnedBy,
+    pub state_scoped: StateScoped<AppState>,
+    pub spatial: SpatialBundle,
+}
+
+impl RingOfFireBundle {
+    pub fn new(owner: Entity, damage: f32, ring: u32) -> Self {
+        Self {
+            name: Name::new("Ring of Fire"),
+            ring_of_fire: RingOfFire::new(damage, ring),
+            owned_by: OwnedBy(owner),
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::default(),
+        }
+    }
+}
+
 #[derive(Resource, Default, Debug, Reflect)]
 #[reflect(Resource, Default, Debug)]
 pub struct OrbResources {
     pub mesh: Option<Handle<Mesh>>,
     pub material: Option<Handle<StandardMaterial>>,
+    pub ring_of_fire_material: Option<Handle<StandardMaterial>>,
+    /// Ring of fire meshes and their shape, by the player and ring they belong to. A mesh is
+    /// replaced in place when its ring changes size.
+    pub ring_of_fire_meshes: HashMap<(Entity, u32), (Torus, Handle<Mesh>)>,
 }
 
 impl OrbResources {
@@ -92,6 +158,22 @@ impl OrbResources {
         }
     }
 
+    pub fn get_or_create_ring_of_fire_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(ref material) = self.ring_of_fire_material {
+            material.clone()
+        } else {
+            let material = materials.add(StandardMaterial {
+                emissive: LinearRgba::rgb(13.99, 4.0, 0.0),
+                ..default()
+            });
+            self.ring_of_fire_material = Some(material.clone());
+            material
+        }
+    }
+
     pub fn get_or_create_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
         if let Some(ref mesh) = self.mesh {
             mesh.clone()
@@ -101,6 +183,37 @@ impl OrbResources {
             mesh
         }
     }
+
+    /// Returns the mesh of a ring of fire. Its size depends on the player's stats, so there is one
+    /// for each size used.
+    /// Returns the mesh of a player's ring of fire, resizing it if the player's stats changed
+    pub fn get_or_create_ring_of_fire_mesh(
+        &mut self,
+        meshes: &mut Assets<Mesh>,
+        owner: Entity,
+        stats: &PlayerStats,
+        ring: u32,
+    ) -> Handle<Mesh> {
+        let torus = Torus {
+            minor_radius: stats.get_attack_size(constants::ORB_RADIUS),
+            major_radius: stats.get_orb_ring_radius(ring),
+        };
+        match self.ring_of_fire_meshes.get_mut(&(owner, ring)) {
+            Some((current, mesh)) => {
+                if *current != torus {
+                    *current = torus;
+                    meshes.insert(mesh.id(), torus.into());
+                }
+                mesh.clone()
+            }
+            None => {
+                let mesh = meshes.add(torus);
+                self.ring_of_fire_meshes
+                    .insert((owner, ring), (torus, mesh.clone()));
+                mesh
+            }
+        }
+    }
 }
 
 fn setup_new_orbs(
@@ -126,15 +239,45 @@ fn setup_new_orbs(
     }
 }
 
+/// Drops the meshes of the last run's rings of fire, as they belong to players that are gone
+fn reset_ring_of_fire_meshes(mut orb_resources: ResMut<OrbResources>) {
+    orb_resources.ring_of_fire_meshes.clear();
+}
+
+fn setup_new_rings_of_fire(
+    mut commands: Commands,
+    query: Query<(Entity, &RingOfFire, &OwnedBy), Added<RingOfFire>>,
+    mut orb_resources: ResMut<OrbResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    stats_query: Query<&PlayerStats>,
+) {
+    for (entity, ring_of_fire, owned_by) in query.iter() {
+        let Ok(stats) = stats_query.get(owned_by.0) else {
+            continue;
+        };
+        let material = orb_resources.get_or_create_ring_of_fire_material(&mut materials);
+        let mesh = orb_resources.get_or_create_ring_of_fire_mesh(
+            &mut meshes,
+            owned_by.0,
+            stats,
+            ring_of_fire.ring,
+        );
+
+        commands.entity(entity).insert((material, mesh));
+    }
+}
+
 fn handle_collision_events(
     mut events: EventReader<CollisionEvent>,
-    mut orb_query: Query<&mut Orb>,
+    mut orb_query: Query<(&mut Orb, &OwnedBy)>,
     mut health_query: Query<&mut Health>,
+    mut damage_writer: EventWriter<DamageEvent>,
 ) {
     for event in events.read() {
         let entity_pairs = [(event.e1, event.e2), (event.e2, event.e1)];
         for (health_entity, orb_entity) in entity_pairs {
-            if let (Ok(mut health), Ok(mut orb)) = (
+            if let (Ok(mut health), Ok((mut orb, owned_by))) = (
                 health_query.get_mut(health_entity),
                 orb_query.get_mut(orb_entity),
             ) {
@@ -142,7 +285,12 @@ fn handle_collision_events(
                 if orb.hits.contains_key(&health_entity) {
                     continue;
                 }
-                health.current -= orb.damage;
+                health.damage(orb.damage, DamageSource::Orb(owned_by.0));
+                damage_writer.send(DamageEvent {
+                    target: health_entity,
+                    amount: orb.damage,
+                    source: DamageSource::Orb(owned_by.0),
+                });
                 orb.hits.insert(
                     health_entity,
                     Timer::from_seconds(constants::ORB_COOLDOWN_SECS, TimerMode::Once),
@@ -170,29 +318,32 @@ fn tick_orb_hits(time: Res<Time>, mut query: Query<&mut Orb>) {
     }
 }
 
-/// System that rotates orbs around the player
+/// System that rotates orbs around the player that owns them
 fn update_orb_transform(
     time: Res<Time>,
-    stats: Res<PlayerStats>,
-    mut query: Query<(&mut Orb, &mut Transform)>,
-    player_query: Query<(&Player, &GlobalTransform)>,
+    mut query: Query<(&mut Orb, &OwnedBy, &mut Transform)>,
+    player_query: Query<(&Player, &GlobalTransform, &PlayerStats)>,
 ) {
-    let speed = stats.get_attack_speed(constants::ORB_MOVEMENT_SPEED);
+    for (mut orb, owned_by, mut transform) in query.iter_mut() {
+        // get the players position and axis so we can rotate around it
+        let Ok((player, player_transform, stats)) = player_query.get(owned_by.0) else {
+            continue;
+        };
+
+        let orbit_radius = stats.get_orb_ring_radius(orb.ring);
+        let direction = stats.get_orb_ring_direction(orb.ring);
 
-    for (mut orb, mut transform) in query.iter_mut() {
         // update the angle for correct movement speed
         let move_angle =
-            get_angle_for_arc_length(speed, constants::ORB_ORBIT_RADIUS) * time.delta_seconds();
-        orb.angle += move_angle;
-
-        // get the players position and axis so we can rotate around it
-        let (player, player_transform) = player_query.single();
+            get_angle_for_arc_length(stats.orb_speed, orbit_radius) * time.delta_seconds();
+        orb.angle += move_angle * direction.sign();
 
         // set the orb's new transform
         *transform = get_orb_transform(
             player_transform.translation(),
             player.up.normalize(),
             orb.angle,
+            orbit_radius,
             stats.get_attack_size(constants::ORB_RADIUS),
         );
     }
@@ -200,56 +351,187 @@ fn update_orb_transform(
 
 fn update_orbs_on_stats_change(
     mut commands: Commands,
-    stats: Res<PlayerStats>,
-    mut orb_query: Query<(Entity, &mut Collider, &mut Transform, &mut Orb)>,
-    player_query: Query<(&Player, &GlobalTransform)>,
+    mut orb_query: Query<(Entity, &OwnedBy, &mut Collider, &mut Transform, &mut Orb)>,
+    player_query: Query<(Entity, &Player, &GlobalTransform, &PlayerStats), Changed<PlayerStats>>,
 ) {
-    let orb_count = orb_query.iter().count();
-    let expected_count = stats.get_amount(stats.extra_orbs + constants::ORB_BASE_AMOUNT);
+    for (player_entity, player, player_transform, stats) in player_query.iter() {
+        let owned_orbs = || {
+            orb_query
+                .iter()
+                .filter(move |(_, owned_by, _, _, _)| owned_by.0 == player_entity)
+        };
 
-    if (orb_count as u32) < expected_count {
-        // de-spawn existing orbs so we can spawn new ones the correct distance apart
-        for e in orb_query.iter().map(|(e, _, _, _)| e) {
-            commands.entity(e).despawn_recursive();
+        // orbs are replaced by rings of fire once evolved
+        if stats.has_evolution(Evolution::RingOfFire) {
+            for (e, _, _, _, _) in owned_orbs() {
+                commands.entity(e).despawn_recursive();
+            }
+            continue;
         }
 
-        // spawn new orbs
-        let angle_spacer = TAU / (expected_count as f32);
-        for index in 0..expected_count {
-            // get the players position and axis so we can position the orb appropriately
-            let (player, player_transform) = player_query.single();
+        let orb_count = owned_orbs().count() as u32;
+        let ring_count = owned_orbs()
+            .map(|(_, _, _, _, orb)| orb.ring + 1)
+            .max()
+            .unwrap_or(0);
+        let orbs_per_ring = stats.get_orbs_per_ring();
+        let expected_count = orbs_per_ring * stats.orb_rings;
+
+        if orb_count != expected_count || ring_count != stats.orb_rings {
+            // de-spawn existing orbs so we can spawn new ones the correct distance apart
+            for (e, _, _, _, _) in owned_orbs() {
+                commands.entity(e).despawn_recursive();
+            }
+
+            // use the players position and axis so we can position the orbs appropriately
             let camera_up = player.up.normalize();
 
-            // spawn a new orb
-            let orb_angle = (index as f32) * angle_spacer;
-            commands.spawn(OrbBundle::new(
-                get_orb_transform(
-                    player_transform.translation(),
-                    camera_up,
-                    orb_angle,
-                    stats.get_attack_size(constants::ORB_RADIUS),
-                ),
-                stats.get_damage(constants::ORB_BASE_DAMAGE),
-                orb_angle,
-            ));
+            // spawn new orbs
+            let angle_spacer = TAU / (orbs_per_ring as f32);
+            for ring in 0..stats.orb_rings {
+                // stagger every second ring so orbs don't line up
+                let ring_offset = (ring % 2) as f32 * angle_spacer / 2.;
+                for index in 0..orbs_per_ring {
+                    // spawn a new orb
+                    let orb_angle = (index as f32) * angle_spacer + ring_offset;
+                    commands.spawn(OrbBundle::new(
+                        player_entity,
+                        get_orb_transform(
+                            player_transform.translation(),
+                            camera_up,
+                            orb_angle,
+                            stats.get_orb_ring_radius(ring),
+                            stats.get_attack_size(constants::ORB_RADIUS),
+                        ),
+                        stats.get_damage(constants::ORB_BASE_DAMAGE),
+                        orb_angle,
+                        ring,
+                    ));
+                }
+            }
+        } else {
+            // update existing orbs
+            for (_, owned_by, mut collider, mut transform, mut orb) in orb_query.iter_mut() {
+                if owned_by.0 != player_entity {
+                    continue;
+                }
+                // update the collider size
+                *collider = Collider::Sphere(stats.get_attack_size(constants::ORB_RADIUS));
+                // update the transform scale
+                transform.scale = Vec3::splat(stats.get_attack_size(constants::ORB_RADIUS));
+                // update the orb
+                orb.damage = stats.get_damage(constants::ORB_BASE_DAMAGE);
+            }
         }
-    } else {
-        // update existing orbs and count them
-        for (_, mut collider, mut transform, mut orb) in orb_query.iter_mut() {
-            // update the collider size
-            *collider = Collider::Sphere(stats.get_attack_size(constants::ORB_RADIUS));
-            // update the transform scale
-            transform.scale = Vec3::splat(stats.get_attack_size(constants::ORB_RADIUS));
-            // update the orb
-            orb.damage = stats.get_damage(constants::ORB_BASE_DAMAGE);
+    }
+}
+
+fn update_rings_of_fire_on_stats_change(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut RingOfFire, &OwnedBy)>,
+    player_query: Query<(Entity, &PlayerStats), Changed<PlayerStats>>,
+    mut orb_resources: ResMut<OrbResources>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (player_entity, stats) in player_query.iter() {
+        let ring_count = if stats.has_evolution(Evolution::RingOfFire) {
+            stats.orb_rings
+        } else {
+            0
+        };
+        let damage = stats.get_damage(constants::ORB_BASE_DAMAGE);
+
+        // update the rings in place, so their tick timers carry on
+        let mut has_ring = vec![false; ring_count as usize];
+        for (e, mut ring_of_fire, owned_by) in query.iter_mut() {
+            if owned_by.0 != player_entity {
+                continue;
+            }
+            if ring_of_fire.ring >= ring_count {
+                commands.entity(e).despawn_recursive();
+                orb_resources
+                    .ring_of_fire_meshes
+                    .remove(&(player_entity, ring_of_fire.ring));
+                continue;
+            }
+            has_ring[ring_of_fire.ring as usize] = true;
+            ring_of_fire.damage = damage;
+            // resizes the mesh the ring already uses
+            orb_resources.get_or_create_ring_of_fire_mesh(
+                &mut meshes,
+                player_entity,
+                stats,
+                ring_of_fire.ring,
+            );
+        }
+
+        for ring in 0..ring_count {
+            if !has_ring[ring as usize] {
+                commands.spawn(RingOfFireBundle::new(player_entity, damage, ring));
+            }
+        }
+    }
+}
+
+/// System that keeps rings of fire centered on their player, flat against the planet's surface
+fn update_ring_of_fire_transform(
+    mut query: Query<(&OwnedBy, &mut Transform), With<RingOfFire>>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) {
+    for (owned_by, mut transform) in query.iter_mut() {
+        let Ok(player_transform) = player_query.get(owned_by.0) else {
+            continue;
+        };
+        let player_pos = player_transform.translation();
+        let rotation = Quat::from_rotation_arc(Vec3::Y, player_pos.normalize());
+        *transform = Transform::from_translation(player_pos).with_rotation(rotation);
+    }
+}
+
+fn tick_rings_of_fire(
+    time: Res<Time>,
+    mut query: Query<(&mut RingOfFire, &OwnedBy)>,
+    mut enemy_query: Query<(Entity, &GlobalTransform, &mut Health), With<Enemy>>,
+    player_query: Query<(&GlobalTransform, &PlayerStats), With<Player>>,
+    mut damage_writer: EventWriter<DamageEvent>,
+) {
+    for (mut ring_of_fire, owned_by) in query.iter_mut() {
+        ring_of_fire.tick_timer.tick(time.delta());
+        if !ring_of_fire.tick_timer.just_finished() {
+            continue;
+        }
+
+        let Ok((player_transform, stats)) = player_query.get(owned_by.0) else {
+            continue;
+        };
+        let player_pos = player_transform.translation();
+        let half_width = stats.get_attack_size(constants::ORB_RADIUS) + constants::ENEMY_SIZE / 2.;
+
+        // damage every enemy touching the ring
+        let orbit_radius = stats.get_orb_ring_radius(ring_of_fire.ring);
+        for (enemy, enemy_transform, mut health) in enemy_query.iter_mut() {
+            let dist = enemy_transform.translation().distance(player_pos);
+            if (dist - orbit_radius).abs() <= half_width {
+                health.damage(ring_of_fire.damage, DamageSource::Orb(owned_by.0));
+                damage_writer.send(DamageEvent {
+                    target: enemy,
+                    amount: ring_of_fire.damage,
+                    source: DamageSource::Orb(owned_by.0),
+                });
+            }
         }
     }
 }
 
-fn get_orb_transform(player_pos: Vec3, camera_up: Vec3, angle: f32, radius: f32) -> Transform {
+fn get_orb_transform(
+    player_pos: Vec3,
+    camera_up: Vec3,
+    angle: f32,
+    orbit_radius: f32,
+    radius: f32,
+) -> Transform {
     // define the initial transform
-    let mut new_transform =
-        Transform::from_translation(player_pos + camera_up * constants::ORB_ORBIT_RADIUS);
+    let mut new_transform = Transform::from_translation(player_pos + camera_up * orbit_radius);
 
     // rotate the orb around the player's axis
     let towards_camera = player_pos.normalize();
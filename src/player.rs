@@ -1,4 +1,4 @@
-use bevy::{color::palettes::css, prelude::*};
+use bevy::{prelude::*, utils::HashMap, window::PrimaryWindow};
 
 use crate::{game_state::*, math::*, *};
 
@@ -7,15 +7,25 @@ pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerResources>()
-            .add_systems(OnEnter(AppState::Game), spawn_initial_player)
+            .add_systems(
+                OnEnter(AppState::Game),
+                // a continued run spawns its players from the snapshot instead
+                spawn_initial_players.run_if(not(resource_exists::<RunSnapshot>)),
+            )
+            .add_systems(
+                PreUpdate,
+                update_player_bindings
+                    .before(update_player_input)
+                    .run_if(in_state(AppState::Game)),
+            )
             .add_systems(
                 Update,
                 (
-                    (handle_input, recover_health).run_if(not_paused),
+                    ((set_move_target, handle_input).chain(), recover_health).run_if(not_paused),
                     setup_new_players,
                     handle_death,
-                    (update_attractor_radius, update_max_health)
-                        .run_if(resource_exists_and_changed::<PlayerStats>),
+                    despawn_orphans,
+                    (update_attractor_radius, update_max_health),
                 )
                     .run_if(in_state(AppState::Game)),
             );
@@ -26,24 +36,25 @@ impl Plugin for PlayerPlugin {
 #[reflect(Resource, Default, Debug)]
 pub struct PlayerResources {
     pub mesh: Option<Handle<Mesh>>,
-    pub material: Option<Handle<StandardMaterial>>,
+    /// One material per character, so each character has its own color
+    pub materials: HashMap<CharacterKind, Handle<StandardMaterial>>,
 }
 
 impl PlayerResources {
     pub fn get_or_create_material(
         &mut self,
         materials: &mut Assets<StandardMaterial>,
+        character: CharacterKind,
     ) -> Handle<StandardMaterial> {
-        if let Some(ref material) = self.material {
-            material.clone()
-        } else {
-            let material = materials.add(StandardMaterial {
-                base_color: css::BLUE.into(),
-                ..default()
-            });
-            self.material = Some(material.clone());
-            material
-        }
+        self.materials
+            .entry(character)
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: character.character().color.into(),
+                    ..default()
+                })
+            })
+            .clone()
     }
 
     pub fn get_or_create_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
@@ -57,154 +68,286 @@ impl PlayerResources {
     }
 }
 
-#[derive(Component, Debug, Reflect)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Debug)]
 pub struct Player {
     pub up: Vec3,
+    /// Index of the player, starting from `0`. Only more than `0` in local co-op.
+    pub index: u32,
+    pub character: CharacterKind,
 }
 
+/// A point on the planet the player walks towards, set by clicking or tapping. Cleared when the
+/// player arrives or moves with other input.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct MoveTarget(pub Option<Vec3>);
+
+/// Marks an entity that belongs to a player, such as an orb or a drone. It is de-spawned when the
+/// player goes down.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct OwnedBy(pub Entity);
+
 #[derive(Bundle)]
 pub struct PlayerBundle {
     pub name: Name,
     pub player: Player,
+    pub stats: PlayerStats,
+    pub input: PlayerInput,
     pub state_scoped: StateScoped<AppState>,
     pub spatial: SpatialBundle,
     pub health: Health,
     pub collider: Collider,
     pub collision_groups: CollisionGroups,
     pub attractor: Attractor,
+    pub dash: Dash,
+    pub move_target: MoveTarget,
+    pub turret_placer: TurretPlacer,
+    pub item_triggers: ItemTriggers,
 }
 
 impl PlayerBundle {
-    pub fn new() -> Self {
+    pub fn new(index: u32, character: CharacterKind, stats: PlayerStats) -> Self {
         let up = Vec3::Y;
-        Self {
-            name: Name::new("Player"),
-            player: Player { up },
-            state_scoped: StateScoped(AppState::Game),
-            spatial: SpatialBundle::from_transform(Transform::from_translation(Vec3::new(
+
+        // spread players out along the surface so they don't start on top of each other
+        let angle = get_angle_for_arc_length(
+            index as f32 * constants::COOP_SPAWN_SPACING,
+            constants::PLANET_RADIUS,
+        );
+        let pos = Quat::from_axis_angle(up, angle)
+            * Vec3::new(
                 0.,
                 0.,
                 constants::PLANET_RADIUS + constants::PLAYER_SIZE / 2.,
-            ))),
-            health: Health::default(),
+            );
+
+        Self {
+            name: Name::new(format!("Player {}", index + 1)),
+            player: Player {
+                up,
+                index,
+                character,
+            },
+            // the bindings are set from [`InputBindings`] once spawned
+            input: PlayerInput::default(),
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::from_transform(Transform::from_translation(pos)),
+            health: Health::new(stats.max_health),
             collider: Collider::Cuboid(Vec3::splat(constants::PLAYER_SIZE)),
             collision_groups: CollisionGroups::new(GROUP_PLAYER, GROUP_ENEMY | GROUP_POINT),
-            attractor: Attractor::new(constants::PLAYER_DEFAULT_ATTRACTOR_RADIUS),
+            attractor: Attractor::new(stats.pickup_radius),
+            dash: Dash::new(stats.dash_charges, stats.dash_cooldown),
+            move_target: MoveTarget::default(),
+            turret_placer: TurretPlacer::default(),
+            item_triggers: ItemTriggers::default(),
+            stats,
         }
     }
 }
 
-fn spawn_initial_player(mut commands: Commands) {
-    commands.spawn(PlayerBundle::new()).with_children(|p| {
-        p.spawn(WeaponBundle::new());
-    });
+fn spawn_initial_players(
+    mut commands: Commands,
+    player_count: Res<PlayerCount>,
+    selected_characters: Res<SelectedCharacters>,
+    meta_progress: Res<MetaProgress>,
+    achievement_progress: Res<AchievementProgress>,
+) {
+    for index in 0..player_count.0 {
+        let character = selected_characters.get(index);
+        let mut stats = meta_progress.starting_stats(character);
+        stats.locked_weapons = achievement_progress.locked_weapons();
+        commands
+            .spawn(PlayerBundle::new(index, character, stats))
+            .with_children(|p| {
+                p.spawn(WeaponBundle::new());
+            });
+    }
+}
+
+/// System that gives new players their bindings, and updates them when actions are re-bound or
+/// gamepads are connected
+fn update_player_bindings(
+    mut query: Query<(&Player, &mut PlayerInput)>,
+    bindings: Res<InputBindings>,
+    gamepads: Res<ConnectedGamepads>,
+    player_count: Res<PlayerCount>,
+) {
+    for (player, mut input) in query.iter_mut() {
+        if bindings.is_changed() || gamepads.is_changed() || input.is_added() {
+            input.bindings = bindings.for_player(player.index, player_count.0, &gamepads);
+        }
+    }
 }
 
 fn setup_new_players(
     mut commands: Commands,
-    query: Query<(Entity, &Transform), Added<Player>>,
+    query: Query<(Entity, &Transform, &Player), Added<Player>>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut player_resources: ResMut<PlayerResources>,
-    stats: Res<PlayerStats>,
 ) {
-    for (entity, transform) in query.iter() {
-        commands.entity(entity).insert((
-            MaterialMeshBundle {
-                mesh: player_resources.get_or_create_mesh(&mut meshes),
-                material: player_resources.get_or_create_material(&mut materials),
-                transform: *transform,
-                ..default()
-            },
-            Attractor::new(stats.pickup_radius),
-        ));
+    for (entity, transform, player) in query.iter() {
+        commands.entity(entity).insert(MaterialMeshBundle {
+            mesh: player_resources.get_or_create_mesh(&mut meshes),
+            material: player_resources.get_or_create_material(&mut materials, player.character),
+            transform: *transform,
+            ..default()
+        });
     }
 }
 
 fn handle_input(
-    keys: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut Player)>,
-    camera_query: Query<&Transform, (With<GameCamera>, Without<Player>)>,
-    stats: Res<PlayerStats>,
+    mut query: Query<(
+        &mut Transform,
+        &mut Player,
+        &mut MoveTarget,
+        &PlayerInput,
+        &PlayerStats,
+    )>,
 ) {
-    for (mut transform, mut player) in query.iter_mut() {
-        let camera_transform = camera_query.single();
-
+    for (mut transform, mut player, mut move_target, input, stats) in query.iter_mut() {
         let angle = get_angle_for_arc_length(stats.move_speed, constants::PLANET_RADIUS)
             * time.delta_seconds();
+        let input = get_movement_input(&input.actions);
 
-        // Y movement
-        let y = if keys.pressed(KeyCode::ArrowUp) {
-            Some(-1.)
-        } else if keys.pressed(KeyCode::ArrowDown) {
-            Some(1.)
+        let (input, angle) = if input != Vec2::ZERO {
+            // other input takes over from clicking
+            move_target.0 = None;
+            (input, angle)
+        } else if let Some(target) = move_target.0 {
+            // walk along the great circle to the target, stopping once there
+            let remaining = transform.translation.angle_between(target);
+            if remaining <= angle {
+                move_target.0 = None;
+            }
+            (
+                get_input_towards(transform.translation, player.up, target),
+                angle.min(remaining),
+            )
         } else {
-            None
+            continue;
         };
-        if let Some(y) = y {
-            // compute horizontal axis (cross product)
-            let camera_up = player.up.normalize();
-            let towards_camera = camera_transform.translation.normalize();
-            let camera_right = camera_up.cross(towards_camera).normalize();
-
-            // rotate around horizontal axis
-            let rot = Quat::from_axis_angle(camera_right, angle * y);
-            transform.rotate_around(Vec3::ZERO, rot);
-
-            // compute new up
-            player.up = towards_camera.cross(camera_right);
-        }
 
-        // X movement
-        let x = if keys.pressed(KeyCode::ArrowLeft) {
-            Some(-1.)
-        } else if keys.pressed(KeyCode::ArrowRight) {
-            Some(1.)
-        } else {
-            None
-        };
-        if let Some(x) = x {
-            // rotate around vertical axis
-            let rot = Quat::from_axis_angle(player.up.normalize(), angle * x);
-            transform.rotate_around(Vec3::ZERO, rot);
+        let rot = get_movement_rotation(transform.translation, player.up, input, angle);
+        transform.rotate_around(Vec3::ZERO, rot);
+
+        // carry the up direction along with the movement so it stays tangent to the surface
+        player.up = (rot * player.up).normalize();
+    }
+}
+
+/// System that sets the player's [`MoveTarget`] to the point on the planet under the cursor or
+/// touch, while it is held down. Only the first player can be moved this way.
+fn set_move_target(
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    interaction_query: Query<&Interaction>,
+    mut query: Query<(&Player, &mut MoveTarget)>,
+) {
+    let cursor_position = if mouse.pressed(MouseButton::Left) {
+        window_query
+            .get_single()
+            .ok()
+            .and_then(|w| w.cursor_position())
+    } else {
+        touches.first_pressed_position()
+    };
+    let Some(cursor_position) = cursor_position else {
+        return;
+    };
+
+    // ignore presses on UI, such as the virtual joystick
+    if interaction_query.iter().any(|i| *i == Interaction::Pressed) {
+        return;
+    }
+
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    let Some(ray) = camera.viewport_to_world(camera_transform, cursor_position) else {
+        return;
+    };
+    let Some(target) =
+        ray_sphere_intersection(ray.origin, *ray.direction, constants::PLANET_RADIUS)
+    else {
+        return;
+    };
+
+    // the pointer controls the first player
+    for (player, mut move_target) in query.iter_mut() {
+        if player.index == 0 {
+            move_target.0 = Some(target);
         }
     }
 }
 
+/// Returns the 2D movement input in the camera's tangent frame, +x to the right and +y up
+pub fn get_movement_input(actions: &ActionState) -> Vec2 {
+    Vec2::new(
+        actions.value(InputAction::MoveRight) - actions.value(InputAction::MoveLeft),
+        actions.value(InputAction::MoveUp) - actions.value(InputAction::MoveDown),
+    )
+}
+
+/// Returns the position of the player closest to `pos`
+pub fn get_nearest_player_pos(
+    pos: Vec3,
+    player_positions: impl Iterator<Item = Vec3>,
+) -> Option<Vec3> {
+    player_positions.min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+}
+
+/// System that removes players that have gone down, and ends the run once every player is down
 fn handle_death(
-    query: Query<&Health, (With<Player>, Changed<Health>)>,
+    mut commands: Commands,
+    query: Query<(Entity, &Health), With<Player>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    for health in query.iter() {
+    let mut alive = 0;
+    for (entity, health) in query.iter() {
         if health.current <= 0. {
-            next_state.set(AppState::GameOver);
+            commands.entity(entity).despawn_recursive();
+        } else {
+            alive += 1;
         }
     }
+    if alive == 0 && !query.is_empty() {
+        next_state.set(AppState::GameOver);
+    }
 }
 
-fn update_attractor_radius(
-    stats: Res<PlayerStats>,
-    mut query: Query<&mut Attractor, With<Player>>,
+/// System that de-spawns entities whose player has gone down
+fn despawn_orphans(
+    mut commands: Commands,
+    query: Query<(Entity, &OwnedBy)>,
+    player_query: Query<(), With<Player>>,
 ) {
-    for mut attractor in query.iter_mut() {
+    for (entity, owned_by) in query.iter() {
+        if !player_query.contains(owned_by.0) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn update_attractor_radius(mut query: Query<(&mut Attractor, &PlayerStats), Changed<PlayerStats>>) {
+    for (mut attractor, stats) in query.iter_mut() {
         attractor.radius = stats.pickup_radius;
     }
 }
 
-fn update_max_health(stats: Res<PlayerStats>, mut query: Query<&mut Health, With<Player>>) {
-    for mut health in query.iter_mut() {
+fn update_max_health(mut query: Query<(&mut Health, &PlayerStats), Changed<PlayerStats>>) {
+    for (mut health, stats) in query.iter_mut() {
         health.max_health = stats.max_health;
     }
 }
 
-fn recover_health(
-    time: Res<Time>,
-    stats: Res<PlayerStats>,
-    mut query: Query<&mut Health, With<Player>>,
-) {
-    for mut health in query.iter_mut() {
+fn recover_health(time: Res<Time>, mut query: Query<(&mut Health, &PlayerStats)>) {
+    for (mut health, stats) in query.iter_mut() {
         if health.current <= stats.max_health {
             health.current =
                 (health.current + stats.recovery * time.delta_seconds()).min(health.max_health);
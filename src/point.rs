@@ -103,17 +103,20 @@ fn handle_collision_events(
     mut commands: Commands,
     mut events: EventReader<CollisionEvent>,
     point_query: Query<&Point>,
-    player_query: Query<&mut Player>,
+    mut player_query: Query<(&PlayerStats, &mut Health), With<Player>>,
     mut score: ResMut<PlayerScore>,
 ) {
     for event in events.read() {
         let entity_pairs = [(event.e1, event.e2), (event.e2, event.e1)];
         for (player_entity, point_entity) in entity_pairs {
-            if let (Ok(_), Ok(_)) = (
-                player_query.get(player_entity),
+            if let (Ok((stats, mut health)), Ok(_)) = (
+                player_query.get_mut(player_entity),
                 point_query.get(point_entity),
             ) {
                 score.add_points(1);
+                if stats.has_item(Item::Siphon) {
+                    health.heal(constants::ITEM_SIPHON_HEAL);
+                }
                 commands.entity(point_entity).despawn_recursive();
             }
         }
@@ -1,3 +1,5 @@
+use std::f32::consts::TAU;
+
 use bevy::{prelude::*, utils::HashSet};
 
 use crate::*;
@@ -11,6 +13,7 @@ impl Plugin for ProjectilePlugin {
             (
                 setup_new_projectiles,
                 handle_collision_events.run_if(on_event::<CollisionEvent>()),
+                tick_orbital_projectiles.run_if(not_paused),
             )
                 .run_if(in_game),
         );
@@ -41,6 +44,59 @@ impl Projectile {
     }
 }
 
+/// A projectile that keeps travelling around the planet along its great circle instead of expiring
+/// after a short lifetime. It gains damage and size for every full lap, and expires once it has
+/// completed `max_laps`.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct OrbitalProjectile {
+    /// Total angle travelled around the planet, in radians
+    pub angle_travelled: f32,
+    /// Number of full laps completed
+    pub laps: u32,
+    /// Number of laps before the projectile expires
+    pub max_laps: u32,
+    /// Position during the last update, used to measure the angle travelled
+    pub last_pos: Vec3,
+    /// Whether the projectile keeps orbiting after `max_laps` instead of expiring, from
+    /// [`Evolution::PerpetualOrbit`]
+    pub perpetual: bool,
+}
+
+impl OrbitalProjectile {
+    pub fn new(pos: Vec3, max_laps: u32) -> Self {
+        Self {
+            angle_travelled: 0.,
+            laps: 0,
+            max_laps,
+            last_pos: pos,
+            perpetual: false,
+        }
+    }
+
+    /// Adds to the angle travelled. Returns the number of laps completed by this movement.
+    pub fn advance(&mut self, angle: f32) -> u32 {
+        self.angle_travelled += angle;
+        let laps = (self.angle_travelled / TAU).floor() as u32;
+        let new_laps = laps - self.laps;
+        self.laps = laps;
+        new_laps
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !self.perpetual && self.laps >= self.max_laps
+    }
+
+    /// Returns how many of the `new_laps` just completed power the projectile up. The lap that
+    /// reaches `max_laps` and any after it don't.
+    pub fn laps_to_power_up(&self, new_laps: u32) -> u32 {
+        let last_powered_lap = self.max_laps.saturating_sub(1);
+        self.laps
+            .min(last_powered_lap)
+            .saturating_sub(self.laps - new_laps)
+    }
+}
+
 #[derive(Bundle)]
 pub struct ProjectileBundle {
     pub name: Name,
@@ -62,7 +118,7 @@ impl ProjectileBundle {
         radius: f32,
         max_passthrough: u32,
     ) -> Self {
-        let height = constants::PLANET_RADIUS + constants::PROJECTILE_HEIGHT + radius;
+        let height = get_projectile_height(radius);
         let normalized_pos = pos.normalize() * height;
 
         Self {
@@ -82,6 +138,57 @@ impl ProjectileBundle {
     }
 }
 
+/// Bundle for an [`OrbitalProjectile`]. Unlike [`ProjectileBundle`] it has no [`Lifetime`], and it
+/// passes through every enemy it hits.
+#[derive(Bundle)]
+pub struct OrbitalProjectileBundle {
+    pub name: Name,
+    pub projectile: Projectile,
+    pub orbital: OrbitalProjectile,
+    pub owned_by: OwnedBy,
+    pub velocity: Velocity,
+    pub state_scoped: StateScoped<AppState>,
+    pub transform: Transform,
+    pub collider: Collider,
+    pub collision_group: CollisionGroups,
+}
+
+impl OrbitalProjectileBundle {
+    pub fn new(
+        owner: Entity,
+        pos: Vec3,
+        axis: Vec3,
+        speed: f32,
+        damage: f32,
+        radius: f32,
+        max_laps: u32,
+    ) -> Self {
+        let height = get_projectile_height(radius);
+        let normalized_pos = pos.normalize() * height;
+
+        Self {
+            name: Name::new("Orbital Projectile"),
+            projectile: Projectile::new(damage, radius, u32::MAX),
+            orbital: OrbitalProjectile::new(normalized_pos, max_laps),
+            owned_by: OwnedBy(owner),
+            velocity: Velocity {
+                pos: height,
+                axis,
+                speed,
+            },
+            state_scoped: StateScoped(AppState::Game),
+            transform: Transform::from_translation(normalized_pos).with_scale(Vec3::splat(radius)),
+            collider: Collider::Sphere(radius),
+            collision_group: CollisionGroups::new(GROUP_PROJECTILE, GROUP_ENEMY),
+        }
+    }
+}
+
+/// Returns the distance from the center of the planet for a projectile of the given radius.
+fn get_projectile_height(radius: f32) -> f32 {
+    constants::PLANET_RADIUS + constants::PROJECTILE_HEIGHT + radius
+}
+
 #[derive(Resource, Default, Debug, Reflect)]
 #[reflect(Resource, Default, Debug)]
 pub struct ProjectileResources {
@@ -128,15 +235,12 @@ fn setup_new_projectiles(
         let material = weapon_resource.get_or_create_material(&mut materials);
         let mesh = weapon_resource.get_or_create_mesh(&mut meshes);
 
-        commands.entity(entity).insert((
-            Name::new("Projectile"),
-            MaterialMeshBundle {
-                material,
-                mesh,
-                transform: *transform,
-                ..default()
-            },
-        ));
+        commands.entity(entity).insert(MaterialMeshBundle {
+            material,
+            mesh,
+            transform: *transform,
+            ..default()
+        });
     }
 }
 
@@ -145,6 +249,7 @@ fn handle_collision_events(
     mut events: EventReader<CollisionEvent>,
     mut projectile_query: Query<&mut Projectile, With<Projectile>>,
     mut health_query: Query<&mut Health>,
+    mut damage_writer: EventWriter<DamageEvent>,
 ) {
     let mut to_despawn = HashSet::<Entity>::new();
     for event in events.read() {
@@ -162,7 +267,12 @@ fn handle_collision_events(
                 if projectile.hits.contains(&health_entity) {
                     continue;
                 }
-                health.current -= projectile.damage;
+                health.damage(projectile.damage, DamageSource::Projectile);
+                damage_writer.send(DamageEvent {
+                    target: health_entity,
+                    amount: projectile.damage,
+                    source: DamageSource::Projectile,
+                });
                 projectile.passthrough_count += 1;
                 projectile.hits.insert(health_entity);
                 if projectile.passthrough_count >= projectile.max_passthrough {
@@ -176,3 +286,78 @@ fn handle_collision_events(
         commands.entity(entity).despawn_recursive();
     }
 }
+
+/// System that counts the laps made by orbital projectiles, powering them up on each lap and
+/// de-spawning them once they reach their maximum number of laps.
+fn tick_orbital_projectiles(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut OrbitalProjectile,
+        &mut Projectile,
+        &mut Velocity,
+        &mut Collider,
+        &mut Transform,
+    )>,
+) {
+    for (entity, mut orbital, mut projectile, mut velocity, mut collider, mut transform) in
+        query.iter_mut()
+    {
+        let angle = orbital.last_pos.angle_between(transform.translation);
+        orbital.last_pos = transform.translation;
+
+        let new_laps = orbital.advance(angle);
+        if new_laps == 0 {
+            continue;
+        }
+        if orbital.is_finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // power up the projectile for each completed lap
+        for _ in 0..orbital.laps_to_power_up(new_laps) {
+            projectile.damage *= constants::ORBITAL_PROJECTILE_LAP_DAMAGE_MULTIPLIER;
+            projectile.radius *= constants::ORBITAL_PROJECTILE_LAP_SIZE_MULTIPLIER;
+        }
+        // allow hitting the same enemies again on the new lap
+        projectile.hits.clear();
+
+        *collider = Collider::Sphere(projectile.radius);
+        transform.scale = Vec3::splat(projectile.radius);
+        velocity.pos = get_projectile_height(projectile.radius);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn orbital_projectile_counts_laps() {
+        let mut orbital = OrbitalProjectile::new(Vec3::Z, 3);
+
+        assert_eq!(orbital.advance(PI), 0);
+        assert_eq!(orbital.laps, 0);
+
+        assert_eq!(orbital.advance(PI), 1);
+        assert_eq!(orbital.laps, 1);
+        assert!(!orbital.is_finished());
+
+        // a single large movement can complete several laps
+        assert_eq!(orbital.advance(TAU * 2.), 2);
+        assert_eq!(orbital.laps, 3);
+        assert!(orbital.is_finished());
+
+        // once evolved it keeps going, but stops powering up at the last lap
+        let mut orbital = OrbitalProjectile::new(Vec3::Z, 3);
+        orbital.perpetual = true;
+        assert_eq!(orbital.advance(TAU), 1);
+        assert_eq!(orbital.laps_to_power_up(1), 1);
+        assert_eq!(orbital.advance(TAU * 3.), 3);
+        assert_eq!(orbital.laps_to_power_up(3), 1);
+        assert!(!orbital.is_finished());
+    }
+}
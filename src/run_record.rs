@@ -0,0 +1,321 @@
+use std::io;
+
+use bevy::{
+    prelude::*,
+    reflect::{serde::TypedReflectSerializer, TypeRegistry},
+    utils::{HashMap, SystemTime},
+};
+
+use crate::*;
+
+pub struct RunRecordPlugin;
+
+impl Plugin for RunRecordPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunHistory>()
+            .register_type::<RunRecord>()
+            .add_systems(OnEnter(AppState::Game), reset_run_history)
+            .add_systems(
+                Update,
+                (
+                    track_player_stats,
+                    track_damage_taken.run_if(on_event::<DamageEvent>()),
+                    sample_kills.run_if(not_paused),
+                )
+                    .run_if(in_game),
+            )
+            .add_systems(OnEnter(AppState::GameOver), save_run_record);
+    }
+}
+
+/// A power up, evolution or item picked on level up
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Debug)]
+pub struct ChosenPowerUp {
+    /// Index of the player that picked it
+    pub player: u32,
+    /// Level reached when it was picked
+    pub level: u32,
+    pub choice: PowerUpChoice,
+}
+
+/// A card picked from the power up menu
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Debug)]
+pub enum PowerUpChoice {
+    PowerUp(PowerUp),
+    Evolution(Evolution),
+    Item(Item),
+}
+
+/// What happened during the run so far, gathered into a [`RunRecord`] when it ends
+#[derive(Resource, Default, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct RunHistory {
+    /// Latest stats of each player, by index. Kept here as players are de-spawned when they go
+    /// down.
+    pub player_stats: Vec<PlayerStats>,
+    /// Power ups in the order they were picked
+    pub power_ups: Vec<ChosenPowerUp>,
+    /// Total enemies killed at the end of each [`constants::RUN_RECORD_SAMPLE_SECS`]
+    pub kills_over_time: Vec<u32>,
+    /// Damage taken by the players, by what dealt it
+    pub damage_taken: HashMap<String, f32>,
+}
+
+impl RunHistory {
+    /// Records the kills for every sample interval that has passed by `elapsed_secs`
+    pub fn sample_kills(&mut self, elapsed_secs: f32, kills: u32) {
+        let samples = (elapsed_secs / constants::RUN_RECORD_SAMPLE_SECS) as usize;
+        while self.kills_over_time.len() < samples {
+            self.kills_over_time.push(kills);
+        }
+    }
+}
+
+/// Summary of a finished run, written as a line of JSON to
+/// [`constants::RUN_RECORD_DIRECTORY`] for studying the game's balance.
+#[derive(Debug, Reflect)]
+#[reflect(Debug)]
+pub struct RunRecord {
+    /// Seconds since the Unix epoch when the run ended
+    pub timestamp: u64,
+    pub duration_secs: f32,
+    /// Seed of the [`GameRng`], to replay the run
+    pub seed: u64,
+    pub player_count: u32,
+    pub level: u32,
+    pub points: u32,
+    pub kills: u32,
+    /// Final stats of each player, by index
+    pub player_stats: Vec<PlayerStats>,
+    pub power_ups: Vec<ChosenPowerUp>,
+    /// Total enemies killed at the end of each [`constants::RUN_RECORD_SAMPLE_SECS`]
+    pub kills_over_time: Vec<u32>,
+    pub damage_taken: HashMap<String, f32>,
+}
+
+impl RunRecord {
+    pub fn new(
+        history: RunHistory,
+        seed: u64,
+        player_count: u32,
+        score: &PlayerScore,
+        timer: &GameTimer,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self {
+            timestamp,
+            duration_secs: timer.0.elapsed_secs(),
+            seed,
+            player_count,
+            level: score.level,
+            points: score.total_points,
+            kills: score.enemies_killed,
+            player_stats: history.player_stats,
+            power_ups: history.power_ups,
+            kills_over_time: history.kills_over_time,
+            damage_taken: history.damage_taken,
+        }
+    }
+
+    /// Returns the record as a single line of JSON
+    pub fn to_json_line(&self, registry: &TypeRegistry) -> serde_json::Result<String> {
+        serde_json::to_string(&TypedReflectSerializer::new(self, registry))
+    }
+}
+
+fn reset_run_history(mut commands: Commands) {
+    commands.insert_resource(RunHistory::default());
+}
+
+fn track_player_stats(
+    query: Query<(&Player, &PlayerStats), Changed<PlayerStats>>,
+    mut history: ResMut<RunHistory>,
+) {
+    for (player, stats) in query.iter() {
+        let index = player.index as usize;
+        if history.player_stats.len() <= index {
+            history
+                .player_stats
+                .resize_with(index + 1, PlayerStats::default);
+        }
+        history.player_stats[index] = stats.clone();
+    }
+}
+
+fn track_damage_taken(
+    mut events: EventReader<DamageEvent>,
+    player_query: Query<(), With<Player>>,
+    mut history: ResMut<RunHistory>,
+) {
+    for event in events.read() {
+        if player_query.contains(event.target) {
+            *history
+                .damage_taken
+                .entry(event.source.to_string())
+                .or_default() += event.amount;
+        }
+    }
+}
+
+fn sample_kills(timer: Res<GameTimer>, score: Res<PlayerScore>, mut history: ResMut<RunHistory>) {
+    history.sample_kills(timer.0.elapsed_secs(), score.enemies_killed);
+}
+
+fn save_run_record(
+    history: Res<RunHistory>,
+    rng: Res<GameRng>,
+    player_count: Res<PlayerCount>,
+    score: Res<PlayerScore>,
+    timer: Res<GameTimer>,
+    registry: Res<AppTypeRegistry>,
+) {
+    let record = RunRecord::new(history.clone(), rng.seed(), player_count.0, &score, &timer);
+    let result = record
+        .to_json_line(&registry.read())
+        .map_err(io::Error::from)
+        .and_then(|line| append_run_record(&line));
+    if let Err(err) = result {
+        warn!("Failed to save run record: {}", err);
+    }
+}
+
+/// Appends a line to the run records file
+#[cfg(not(target_arch = "wasm32"))]
+fn append_run_record(line: &str) -> io::Result<()> {
+    use std::io::Write;
+
+    let directory = std::path::Path::new(constants::RUN_RECORD_DIRECTORY);
+    std::fs::create_dir_all(directory)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(directory.join(constants::RUN_RECORD_FILE))?;
+    writeln!(file, "{}", line)
+}
+
+/// Web builds can't write files, so the record is logged to the console instead
+#[cfg(target_arch = "wasm32")]
+fn append_run_record(line: &str) -> io::Result<()> {
+    info!("Run record: {}", line);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_are_sampled_at_each_interval() {
+        struct TestCase {
+            name: &'static str,
+            samples: Vec<(f32, u32)>,
+            expected: Vec<u32>,
+        }
+        let interval = constants::RUN_RECORD_SAMPLE_SECS;
+        let test_cases = [
+            TestCase {
+                name: "nothing before the first interval ends",
+                samples: vec![(5., 3)],
+                expected: vec![],
+            },
+            TestCase {
+                name: "one sample per interval",
+                samples: vec![
+                    (5., 3),
+                    (interval, 8),
+                    (interval * 1.5, 10),
+                    (interval * 2., 15),
+                ],
+                expected: vec![8, 15],
+            },
+            TestCase {
+                name: "a long frame can pass several intervals at once",
+                samples: vec![(5., 3), (interval * 2.5, 12)],
+                expected: vec![12, 12],
+            },
+        ];
+        for test in test_cases {
+            let mut history = RunHistory::default();
+            for (elapsed_secs, kills) in test.samples {
+                history.sample_kills(elapsed_secs, kills);
+            }
+            assert_eq!(history.kills_over_time, test.expected, "{}", test.name);
+        }
+    }
+
+    #[test]
+    fn run_record_is_a_line_of_json() {
+        let mut history = RunHistory::default();
+        history.sample_kills(constants::RUN_RECORD_SAMPLE_SECS, 12);
+        history
+            .damage_taken
+            .insert(DamageSource::Enemy.to_string(), 25.);
+        history.player_stats.push(PlayerStats::default());
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<RunRecord>();
+        let record = RunRecord::new(
+            history,
+            42,
+            1,
+            &PlayerScore::default(),
+            &GameTimer::default(),
+        );
+        let line = record.to_json_line(&registry).unwrap();
+        assert!(!line.contains('\n'));
+
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["seed"], 42);
+        assert_eq!(json["damage_taken"]["Enemy"], 25.);
+        assert_eq!(json["kills_over_time"], serde_json::json!([12]));
+        assert_eq!(
+            json["player_stats"][0]["max_health"],
+            PlayerStats::default().max_health
+        );
+    }
+
+    #[test]
+    fn run_record_lists_every_pick_with_its_level() {
+        let mut history = RunHistory::default();
+        let picks = [
+            PowerUpChoice::PowerUp(PowerUp {
+                stat: Stat::MaxHealth,
+                value: PowerUpValue::Percent(10),
+                rarity: Rarity::Common,
+            }),
+            PowerUpChoice::Evolution(Evolution::Nova),
+            PowerUpChoice::Item(Item::Siphon),
+        ];
+        for (level, choice) in (2..).zip(picks) {
+            history.power_ups.push(ChosenPowerUp {
+                player: 0,
+                level,
+                choice,
+            });
+        }
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<RunRecord>();
+        let record = RunRecord::new(
+            history,
+            42,
+            1,
+            &PlayerScore::default(),
+            &GameTimer::default(),
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&record.to_json_line(&registry).unwrap()).unwrap();
+        let power_ups = &json["power_ups"];
+        assert_eq!(power_ups[0]["level"], 2);
+        assert_eq!(power_ups[0]["choice"]["PowerUp"]["stat"], "MaxHealth");
+        assert_eq!(power_ups[1]["level"], 3);
+        assert_eq!(power_ups[1]["choice"]["Evolution"], "Nova");
+        assert_eq!(power_ups[2]["level"], 4);
+        assert_eq!(power_ups[2]["choice"]["Item"], "Siphon");
+    }
+}
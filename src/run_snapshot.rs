@@ -0,0 +1,311 @@
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        TypeRegistry,
+    },
+};
+use serde::de::{DeserializeSeed, Error as _};
+
+use crate::*;
+
+pub struct RunSnapshotPlugin;
+
+impl Plugin for RunSnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RunSnapshot>().add_systems(
+            Update,
+            restore_run_snapshot.run_if(in_game.and_then(resource_exists::<RunSnapshot>)),
+        );
+    }
+}
+
+/// A run in progress, saved when quitting from the pause menu so it can be continued from the
+/// main menu. It is serialized through the [`Reflect`] implementations of the types it holds.
+///
+/// While this is inserted as a resource the players aren't spawned when the game starts, and it
+/// is restored on the first frame of the game instead.
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct RunSnapshot {
+    pub player_count: u32,
+    /// Players still standing
+    pub players: Vec<PlayerSnapshot>,
+    pub enemies: Vec<EnemySnapshot>,
+    /// Positions of the points waiting to be picked up
+    pub points: Vec<Vec3>,
+    pub enemy_stats: EnemyStats,
+    pub score: PlayerScore,
+    pub timer: GameTimer,
+    pub spawner: EnemySpawner,
+    pub history: RunHistory,
+    pub ultimate: UltimateMeter,
+    /// Seed of the [`GameRng`]
+    pub seed: u64,
+    /// How far each stream of the [`GameRng`] had got, so a continued run rolls the same numbers
+    /// it would have without quitting
+    pub rng_positions: Vec<u128>,
+}
+
+#[derive(Debug, Reflect)]
+#[reflect(Debug)]
+pub struct PlayerSnapshot {
+    pub player: Player,
+    pub stats: PlayerStats,
+    pub transform: Transform,
+    pub health: Health,
+}
+
+#[derive(Debug, Reflect)]
+#[reflect(Debug)]
+pub struct EnemySnapshot {
+    pub enemy: Enemy,
+    pub transform: Transform,
+    pub health: Health,
+}
+
+impl RunSnapshot {
+    pub const STORAGE_KEY: &'static str = "run";
+
+    /// Returns a snapshot of the run in a world
+    pub fn capture(world: &mut World) -> Self {
+        let mut players: Vec<PlayerSnapshot> = world
+            .query::<(&Player, &PlayerStats, &Transform, &Health)>()
+            .iter(world)
+            .map(|(player, stats, transform, health)| PlayerSnapshot {
+                player: player.clone(),
+                stats: stats.clone(),
+                transform: *transform,
+                health: snapshot_health(health),
+            })
+            .collect();
+        players.sort_by_key(|snapshot| snapshot.player.index);
+
+        let enemies = world
+            .query::<(&Enemy, &Transform, &Health)>()
+            .iter(world)
+            .map(|(enemy, transform, health)| EnemySnapshot {
+                enemy: enemy.clone(),
+                transform: *transform,
+                health: snapshot_health(health),
+            })
+            .collect();
+
+        let points = world
+            .query_filtered::<&Transform, With<Point>>()
+            .iter(world)
+            .map(|transform| transform.translation)
+            .collect();
+
+        Self {
+            player_count: world.resource::<PlayerCount>().0,
+            players,
+            enemies,
+            points,
+            enemy_stats: world.resource::<EnemyStats>().clone(),
+            score: world.resource::<PlayerScore>().clone(),
+            timer: world.resource::<GameTimer>().clone(),
+            spawner: world.resource::<EnemySpawner>().clone(),
+            history: world.resource::<RunHistory>().clone(),
+            ultimate: world.resource::<UltimateMeter>().clone(),
+            seed: world.resource::<GameRng>().seed(),
+            rng_positions: world.resource::<GameRng>().positions(),
+        }
+    }
+
+    /// Spawns the players, enemies and points of the snapshot, and replaces the run's resources
+    pub fn restore(self, world: &mut World) {
+        for snapshot in self.players {
+            let mut bundle = PlayerBundle::new(
+                snapshot.player.index,
+                snapshot.player.character,
+                snapshot.stats,
+            );
+            bundle.player = snapshot.player;
+            bundle.spatial.transform = snapshot.transform;
+            bundle.health = snapshot.health;
+            world.spawn(bundle).with_children(|p| {
+                p.spawn(WeaponBundle::new());
+            });
+        }
+
+        for snapshot in self.enemies {
+            // the enemy's speed and damage are replaced by the snapshot's
+            let mut bundle = EnemyBundle::new(
+                snapshot.transform.translation,
+                snapshot.health.max_health,
+                0.,
+                0.,
+            );
+            bundle.enemy = snapshot.enemy;
+            bundle.spatial_bundle.transform = snapshot.transform;
+            bundle.health = snapshot.health;
+            world.spawn(bundle);
+        }
+
+        for pos in self.points {
+            world.spawn(PointBundle::new(pos));
+        }
+
+        world.insert_resource(self.enemy_stats);
+        world.insert_resource(self.score);
+        world.insert_resource(self.timer);
+        world.insert_resource(self.spawner);
+        world.insert_resource(self.history);
+        world.insert_resource(self.ultimate);
+        world.insert_resource(GameRng::with_positions(self.seed, &self.rng_positions));
+    }
+
+    pub fn to_json(&self, registry: &TypeRegistry) -> serde_json::Result<String> {
+        serde_json::to_string(&TypedReflectSerializer::new(self, registry))
+    }
+
+    pub fn from_json(json: &str, registry: &TypeRegistry) -> serde_json::Result<Self> {
+        let registration = registry
+            .get(TypeId::of::<Self>())
+            .ok_or_else(|| serde_json::Error::custom("run snapshot type is not registered"))?;
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let value =
+            TypedReflectDeserializer::new(registration, registry).deserialize(&mut deserializer)?;
+        Self::from_reflect(&*value)
+            .ok_or_else(|| serde_json::Error::custom("saved run doesn't match the run snapshot"))
+    }
+}
+
+/// Copies health, without the damage source as the entity it may refer to won't survive a reload
+fn snapshot_health(health: &Health) -> Health {
+    Health {
+        last_damage_source: None,
+        ..health.clone()
+    }
+}
+
+/// Saves the run in progress to [`GameStorage`], replacing any run saved before
+pub fn save_run(world: &mut World) {
+    let snapshot = RunSnapshot::capture(world);
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let storage = world.resource::<GameStorage>();
+    let result = snapshot
+        .to_json(&registry)
+        .map_err(std::io::Error::from)
+        .and_then(|json| storage.save(RunSnapshot::STORAGE_KEY, &json));
+    if let Err(err) = result {
+        warn!("Failed to save run: {}", err);
+    }
+}
+
+/// Loads the run saved in [`GameStorage`] and starts the game from it. A saved run can only be
+/// continued once, so it is removed from storage.
+pub fn continue_saved_run(world: &mut World) {
+    let snapshot = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let storage = world.resource::<GameStorage>();
+        let Some(json) = storage.load(RunSnapshot::STORAGE_KEY) else {
+            return;
+        };
+        if let Err(err) = storage.remove(RunSnapshot::STORAGE_KEY) {
+            warn!("Failed to remove saved run: {}", err);
+        }
+        match RunSnapshot::from_json(&json, &registry) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                warn!("Failed to read saved run: {}", err);
+                return;
+            }
+        }
+    };
+
+    // the HUD is set up for the player count and characters when the game starts
+    world.resource_mut::<PlayerCount>().0 = snapshot.player_count;
+    let mut selected_characters = world.resource_mut::<SelectedCharacters>();
+    for player in snapshot.players.iter().map(|snapshot| &snapshot.player) {
+        selected_characters.set(player.index, player.character);
+    }
+
+    world.insert_resource(snapshot);
+    world
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::Game);
+}
+
+/// Returns `true` if there is a run saved that can be continued
+pub fn has_saved_run(storage: &GameStorage) -> bool {
+    storage.load(RunSnapshot::STORAGE_KEY).is_some()
+}
+
+fn restore_run_snapshot(world: &mut World) {
+    if let Some(snapshot) = world.remove_resource::<RunSnapshot>() {
+        snapshot.restore(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn run_snapshot_round_trips_through_json() {
+        let mut world = World::new();
+        world.insert_resource(PlayerCount(2));
+        world.insert_resource(EnemyStats {
+            health: 42.,
+            ..default()
+        });
+        world.insert_resource(PlayerScore {
+            level: 3,
+            total_points: 120,
+            enemies_killed: 35,
+            ..default()
+        });
+        let mut timer = GameTimer::default();
+        timer.0.tick(std::time::Duration::from_secs_f32(95.5));
+        world.insert_resource(timer);
+        world.insert_resource(EnemySpawner {
+            has_started: true,
+            ..default()
+        });
+        let mut history = RunHistory::default();
+        history.sample_kills(constants::RUN_RECORD_SAMPLE_SECS, 20);
+        world.insert_resource(history);
+        world.insert_resource(UltimateMeter {
+            charge: 40.,
+            uses: 2,
+        });
+        let mut rng = GameRng::new(7);
+        rng.stream(RngStream::PowerUps).gen::<u32>();
+        world.insert_resource(rng);
+
+        let mut stats = PlayerStats::default();
+        stats.add_item(Item::Siphon);
+        let mut player = PlayerBundle::new(1, CharacterKind::default(), stats);
+        player.health.damage(12.5, DamageSource::Enemy);
+        world.spawn(player);
+        let mut enemy = EnemyBundle::new(Vec3::new(1., 2., 3.), 30., 4., 5.);
+        enemy.health.damage(7., DamageSource::Projectile);
+        world.spawn(enemy);
+        world.spawn(PointBundle::new(Vec3::X));
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<RunSnapshot>();
+
+        let snapshot = RunSnapshot::capture(&mut world);
+        let json = snapshot.to_json(&registry).unwrap();
+        let loaded = RunSnapshot::from_json(&json, &registry).unwrap();
+        assert_eq!(format!("{:?}", loaded), format!("{:?}", snapshot));
+
+        // restoring into an empty world gives back the same run
+        let mut restored = World::new();
+        restored.insert_resource(PlayerCount(2));
+        loaded.restore(&mut restored);
+        let restored = RunSnapshot::capture(&mut restored);
+        assert_eq!(format!("{:?}", restored), format!("{:?}", snapshot));
+        let player = &restored.players[0];
+        assert_eq!(player.health.current, player.stats.max_health - 12.5);
+        assert_eq!(restored.score.enemies_killed, 35);
+        assert_eq!(restored.ultimate.uses, 2);
+    }
+}
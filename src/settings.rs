@@ -0,0 +1,287 @@
+use std::fmt;
+
+use bevy::{
+    audio::Volume,
+    prelude::*,
+    window::{PrimaryWindow, WindowFocused, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{constants, game_state::*, GameStorage};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>()
+            .add_systems(Startup, load_settings)
+            .add_systems(
+                Update,
+                (
+                    (
+                        apply_audio_settings,
+                        apply_window_settings,
+                        apply_ui_settings,
+                    )
+                        .run_if(resource_changed::<Settings>),
+                    pause_on_focus_lost.run_if(in_game_not_paused),
+                ),
+            );
+    }
+}
+
+/// Options chosen on the settings screen. Saved to [`GameStorage`] under
+/// [`Settings::STORAGE_KEY`], and applied as soon as they change.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Resource, Default, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// Volume of every sound, from `0` to `1`
+    pub master_volume: f32,
+    /// Volume of the music, from `0` to `1`, scaled by the master volume
+    pub music_volume: f32,
+    /// Volume of sound effects, from `0` to `1`, scaled by the master volume
+    pub sfx_volume: f32,
+    pub fullscreen: bool,
+    pub bloom: bool,
+    pub fog: bool,
+    /// Scale of the menus and HUD, one of [`constants::SETTINGS_UI_SCALES`]
+    pub ui_scale: f32,
+    /// Multiplier of how far the camera shakes, from `0` to `1`
+    pub screen_shake: f32,
+    /// Whether the game pauses when its window loses focus
+    pub auto_pause: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.,
+            music_volume: 1.,
+            sfx_volume: 1.,
+            fullscreen: false,
+            bloom: true,
+            fog: true,
+            ui_scale: 1.,
+            screen_shake: 1.,
+            auto_pause: true,
+        }
+    }
+}
+
+impl Settings {
+    pub const STORAGE_KEY: &'static str = "settings";
+
+    /// Returns the volume of an audio channel, including the master volume
+    pub fn volume(&self, channel: AudioChannel) -> f32 {
+        let volume = match channel {
+            AudioChannel::Music => self.music_volume,
+            AudioChannel::SoundEffects => self.sfx_volume,
+        };
+        self.master_volume * volume
+    }
+}
+
+/// Tags an audio source so its volume follows the music or sound effects setting. No audio is
+/// played yet, so nothing is tagged, but audio added later only needs to insert it.
+#[allow(dead_code)]
+#[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq)]
+pub enum AudioChannel {
+    Music,
+    SoundEffects,
+}
+
+/// A setting shown on the settings screen. Pressing its button moves it to the next value.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+pub enum SettingOption {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    Fullscreen,
+    Bloom,
+    Fog,
+    UiScale,
+    ScreenShake,
+    AutoPause,
+}
+
+impl SettingOption {
+    pub const ALL: [Self; 9] = [
+        Self::MasterVolume,
+        Self::MusicVolume,
+        Self::SfxVolume,
+        Self::Fullscreen,
+        Self::Bloom,
+        Self::Fog,
+        Self::UiScale,
+        Self::ScreenShake,
+        Self::AutoPause,
+    ];
+
+    pub fn value_label(&self, settings: &Settings) -> String {
+        let percent = |value: f32| format!("{:.0}%", value * 100.);
+        let on_off = |value: bool| if value { "On" } else { "Off" }.to_string();
+        match self {
+            SettingOption::MasterVolume => percent(settings.master_volume),
+            SettingOption::MusicVolume => percent(settings.music_volume),
+            SettingOption::SfxVolume => percent(settings.sfx_volume),
+            SettingOption::Fullscreen if settings.fullscreen => "Fullscreen".to_string(),
+            SettingOption::Fullscreen => "Windowed".to_string(),
+            SettingOption::Bloom => on_off(settings.bloom),
+            SettingOption::Fog => on_off(settings.fog),
+            SettingOption::UiScale => percent(settings.ui_scale),
+            SettingOption::ScreenShake => percent(settings.screen_shake),
+            SettingOption::AutoPause => on_off(settings.auto_pause),
+        }
+    }
+
+    /// Moves the setting to its next value, wrapping around to the first after the last
+    pub fn cycle(&self, settings: &mut Settings) {
+        let volume_step = constants::SETTINGS_VOLUME_STEP;
+        match self {
+            SettingOption::MasterVolume => {
+                settings.master_volume = next_step(settings.master_volume, volume_step);
+            }
+            SettingOption::MusicVolume => {
+                settings.music_volume = next_step(settings.music_volume, volume_step);
+            }
+            SettingOption::SfxVolume => {
+                settings.sfx_volume = next_step(settings.sfx_volume, volume_step);
+            }
+            SettingOption::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            SettingOption::Bloom => settings.bloom = !settings.bloom,
+            SettingOption::Fog => settings.fog = !settings.fog,
+            SettingOption::UiScale => {
+                let scales = constants::SETTINGS_UI_SCALES;
+                let current = scales
+                    .iter()
+                    .position(|scale| *scale >= settings.ui_scale)
+                    .unwrap_or(scales.len() - 1);
+                settings.ui_scale = scales[(current + 1) % scales.len()];
+            }
+            SettingOption::ScreenShake => {
+                settings.screen_shake =
+                    next_step(settings.screen_shake, constants::SETTINGS_SCREEN_SHAKE_STEP);
+            }
+            SettingOption::AutoPause => settings.auto_pause = !settings.auto_pause,
+        }
+    }
+}
+
+impl fmt::Display for SettingOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingOption::MasterVolume => write!(f, "Master Volume"),
+            SettingOption::MusicVolume => write!(f, "Music Volume"),
+            SettingOption::SfxVolume => write!(f, "Effects Volume"),
+            SettingOption::Fullscreen => write!(f, "Display"),
+            SettingOption::Bloom => write!(f, "Bloom"),
+            SettingOption::Fog => write!(f, "Fog"),
+            SettingOption::UiScale => write!(f, "UI Scale"),
+            SettingOption::ScreenShake => write!(f, "Screen Shake"),
+            SettingOption::AutoPause => write!(f, "Pause When Unfocused"),
+        }
+    }
+}
+
+/// Returns the next multiple of `step` after `value`, going back to `0` after `1`
+fn next_step(value: f32, step: f32) -> f32 {
+    let steps = (1. / step).round() as u32;
+    let next = (value.clamp(0., 1.) / step).round() as u32 + 1;
+    (next % (steps + 1)) as f32 * step
+}
+
+fn load_settings(mut commands: Commands, storage: Res<GameStorage>) {
+    if let Some(settings) = storage.load_json::<Settings>(Settings::STORAGE_KEY) {
+        commands.insert_resource(settings);
+    }
+}
+
+fn apply_audio_settings(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    sink_query: Query<(&AudioSink, &AudioChannel)>,
+) {
+    // the global volume only applies to sounds started after it changes
+    global_volume.volume = Volume::new(settings.master_volume);
+    for (sink, channel) in sink_query.iter() {
+        sink.set_volume(settings.volume(*channel));
+    }
+}
+
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    };
+    for mut window in window_query.iter_mut() {
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+}
+
+fn apply_ui_settings(settings: Res<Settings>, mut ui_scale: ResMut<UiScale>) {
+    ui_scale.0 = settings.ui_scale;
+}
+
+/// System that pauses the game when its window loses focus, if the setting is on
+fn pause_on_focus_lost(
+    mut events: EventReader<WindowFocused>,
+    settings: Res<Settings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if events.read().any(|event| !event.focused) && settings.auto_pause {
+        next_state.set(GameState::Paused);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_cycle_through_their_values() {
+        let mut settings = Settings::default();
+
+        // full volume wraps around to muted
+        SettingOption::MasterVolume.cycle(&mut settings);
+        assert_eq!(settings.master_volume, 0.);
+        for _ in 0..5 {
+            SettingOption::MasterVolume.cycle(&mut settings);
+        }
+        assert!((settings.master_volume - 0.5).abs() < f32::EPSILON);
+        assert_eq!(SettingOption::MasterVolume.value_label(&settings), "50%");
+        assert!((settings.volume(AudioChannel::Music) - 0.5).abs() < f32::EPSILON);
+
+        let mut scales = Vec::new();
+        for _ in constants::SETTINGS_UI_SCALES {
+            SettingOption::UiScale.cycle(&mut settings);
+            scales.push(settings.ui_scale);
+        }
+        assert_eq!(scales.last(), Some(&1.));
+        assert_eq!(scales.len(), constants::SETTINGS_UI_SCALES.len());
+
+        SettingOption::Fullscreen.cycle(&mut settings);
+        assert_eq!(
+            SettingOption::Fullscreen.value_label(&settings),
+            "Fullscreen"
+        );
+
+        // settings saved by older versions keep the defaults of anything added since
+        let loaded: Settings = serde_json::from_str(r#"{"bloom":false}"#).unwrap();
+        assert_eq!(
+            loaded,
+            Settings {
+                bloom: false,
+                ..default()
+            }
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::io;
+#[cfg(test)]
+use std::{collections::HashMap, sync::Mutex};
+
+use bevy::{
+    prelude::*,
+    reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        TypeRegistry,
+    },
+};
+use serde::{
+    de::{DeserializeOwned, DeserializeSeed, Error as _},
+    Serialize,
+};
+
+pub struct StoragePlugin;
+
+impl Plugin for StoragePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameStorage>();
+    }
+}
+
+/// Somewhere to keep data between runs of the game, as strings stored by key.
+pub trait Storage: Send + Sync + 'static {
+    /// Returns the value stored for a key, or `None` if nothing has been stored
+    fn load(&self, key: &str) -> Option<String>;
+
+    fn save(&self, key: &str, value: &str) -> io::Result<()>;
+
+    /// Removes the value stored for a key. Removing a key that was never stored is not an error.
+    fn remove(&self, key: &str) -> io::Result<()>;
+}
+
+/// Stores each key as a file in a directory. Used by native builds.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage {
+    pub directory: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    pub fn new(directory: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        self.directory.join(format!("{}.json", key))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)).ok()
+    }
+
+    fn save(&self, key: &str, value: &str) -> io::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        std::fs::write(self.path(key), value)
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+/// Stores each key in the browser's local storage. Used by wasm builds.
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+    /// Prepended to every key, so the game's keys don't clash with other pages on the same host
+    pub prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn storage() -> io::Result<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| io::Error::other("local storage is not available"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        let key = format!("{}.{}", self.prefix, key);
+        Self::storage().ok()?.get_item(&key).ok().flatten()
+    }
+
+    fn save(&self, key: &str, value: &str) -> io::Result<()> {
+        let key = format!("{}.{}", self.prefix, key);
+        Self::storage()?
+            .set_item(&key, value)
+            .map_err(|_| io::Error::other("failed to write to local storage"))
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        let key = format!("{}.{}", self.prefix, key);
+        Self::storage()?
+            .remove_item(&key)
+            .map_err(|_| io::Error::other("failed to remove from local storage"))
+    }
+}
+
+/// Keeps everything in memory, so tests can run headless without touching the disk.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStorage(Mutex<HashMap<String, String>>);
+
+#[cfg(test)]
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.0.lock().ok()?.get(key).cloned()
+    }
+
+    fn save(&self, key: &str, value: &str) -> io::Result<()> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("memory storage lock was poisoned"))?
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("memory storage lock was poisoned"))?
+            .remove(key);
+        Ok(())
+    }
+}
+
+/// The storage used by the game. Defaults to files on native builds and local storage on wasm.
+#[derive(Resource, Deref)]
+pub struct GameStorage(pub Box<dyn Storage>);
+
+impl Default for GameStorage {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default() -> Self {
+        Self(Box::new(FileStorage::new(crate::constants::SAVE_DIRECTORY)))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn default() -> Self {
+        Self(Box::new(LocalStorage::new(crate::constants::APP_NAME)))
+    }
+}
+
+impl GameStorage {
+    /// Loads a value saved with [`GameStorage::save_json`]. Returns `None` if nothing was saved or
+    /// it can't be read.
+    pub fn load_json<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.load(key)?;
+        match serde_json::from_str(&value) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                warn!("Failed to read saved {}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    /// Saves a value as JSON, logging a warning if it can't be saved
+    pub fn save_json<T: Serialize>(&self, key: &str, value: &T) {
+        let result = serde_json::to_string(value)
+            .map_err(io::Error::from)
+            .and_then(|value| self.save(key, &value));
+        if let Err(err) = result {
+            warn!("Failed to save {}: {}", key, err);
+        }
+    }
+
+    /// Loads a value saved with [`GameStorage::save_reflect`]. Returns `None` if nothing was saved
+    /// or it can't be read.
+    pub fn load_reflect<T: FromReflect>(&self, key: &str, registry: &TypeRegistry) -> Option<T> {
+        let value = self.load(key)?;
+        let result = registry
+            .get(std::any::TypeId::of::<T>())
+            .ok_or_else(|| serde_json::Error::custom("type is not registered"))
+            .and_then(|registration| {
+                let mut deserializer = serde_json::Deserializer::from_str(&value);
+                TypedReflectDeserializer::new(registration, registry).deserialize(&mut deserializer)
+            })
+            .and_then(|value| {
+                T::from_reflect(&*value)
+                    .ok_or_else(|| serde_json::Error::custom("saved value doesn't match its type"))
+            });
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                warn!("Failed to read saved {}: {}", key, err);
+                None
+            }
+        }
+    }
+
+    /// Saves a value as JSON through its [`Reflect`] implementation, for types that can't derive
+    /// [`Serialize`], logging a warning if it can't be saved
+    pub fn save_reflect<T: Reflect>(&self, key: &str, value: &T, registry: &TypeRegistry) {
+        let result = serde_json::to_string(&TypedReflectSerializer::new(value, registry))
+            .map_err(io::Error::from)
+            .and_then(|value| self.save(key, &value));
+        if let Err(err) = result {
+            warn!("Failed to save {}: {}", key, err);
+        }
+    }
+}
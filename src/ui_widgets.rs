@@ -1,5 +1,7 @@
 mod button;
+mod focus;
 mod plugin;
 mod progress_bar;
+mod text_input;
 
-pub use self::{button::*, plugin::*, progress_bar::*};
+pub use self::{button::*, focus::*, plugin::*, progress_bar::*, text_input::*};
@@ -10,13 +10,13 @@ impl Plugin for MenuButtonPlugin {
     }
 }
 
-const BG_PRESSED: Srgba = css::RED;
+const BG_PRESSED: Srgba = css::DARK_RED;
 const BG_HOVERED: Srgba = css::RED;
-const BG_NONE: Srgba = css::RED;
+const BG_NONE: Srgba = css::FIRE_BRICK;
 
 const BORDER_PRESSED: Srgba = css::WHITE;
 const BORDER_HOVERED: Srgba = css::WHITE;
-const BORDER_NONE: Srgba = css::WHITE;
+const BORDER_NONE: Srgba = css::SILVER;
 
 #[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component, Default, Debug)]
@@ -37,7 +37,7 @@ pub fn button_widget(
         },
         border_radius: BorderRadius::all(Val::Px(5.)),
         background_color: BG_NONE.into(),
-        border_color: Color::WHITE.into(),
+        border_color: BORDER_NONE.into(),
         ..default()
     };
     class.apply(&mut bundle);
@@ -79,7 +79,7 @@ fn handle_interactions(
     for (interaction, mut bg, mut border) in query.iter_mut() {
         let (new_bg, new_border) = match *interaction {
             Interaction::Pressed => (BG_PRESSED, BORDER_PRESSED),
-            Interaction::Hovered => (BG_HOVERED.darker(0.1), BORDER_HOVERED),
+            Interaction::Hovered => (BG_HOVERED, BORDER_HOVERED),
             Interaction::None => (BG_NONE, BORDER_NONE),
         };
         *bg = new_bg.into();
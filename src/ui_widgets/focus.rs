@@ -0,0 +1,190 @@
+use bevy::{color::palettes::css, prelude::*, ui::UiSystem};
+
+use crate::{update_action_state, ActionState, InputAction};
+
+use super::{release_text_inputs_on_gamepad_input, MenuButton, TextInput};
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (focus_hovered_buttons, handle_focus_input)
+                .chain()
+                .after(UiSystem::Focus)
+                .after(update_action_state)
+                .after(release_text_inputs_on_gamepad_input),
+        )
+        .add_systems(Update, update_focus_outline);
+    }
+}
+
+const FOCUS_OUTLINE_COLOR: Srgba = css::GOLD;
+
+/// Marks the [`MenuButton`] that is activated by [`InputAction::Confirm`]. Menus can insert it to
+/// choose which button starts out focused, otherwise the top-left button is focused.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Focused;
+
+/// System that moves focus to a button when the mouse hovers it, so only one button is highlighted
+fn focus_hovered_buttons(
+    mut commands: Commands,
+    query: Query<(Entity, &Interaction), (With<MenuButton>, Changed<Interaction>)>,
+    focused_query: Query<Entity, With<Focused>>,
+) {
+    for (entity, interaction) in query.iter() {
+        if *interaction == Interaction::Hovered && !focused_query.contains(entity) {
+            for focused in focused_query.iter() {
+                commands.entity(focused).remove::<Focused>();
+            }
+            commands.entity(entity).insert(Focused);
+        }
+    }
+}
+
+/// System that moves focus between buttons with the movement actions, and presses the focused
+/// button on confirm. Paused while text is being typed into a [`TextInput`], which a gamepad
+/// leaves.
+fn handle_focus_input(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut query: Query<(Entity, &GlobalTransform, &mut Interaction, Has<Focused>), With<MenuButton>>,
+    text_input_query: Query<&TextInput>,
+    mut last_pressed: Local<Option<Entity>>,
+) {
+    // release the button pressed last frame, so it can be pressed again
+    if let Some(entity) = last_pressed.take() {
+        if let Ok((_, _, mut interaction, _)) = query.get_mut(entity) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+
+    if text_input_query.iter().any(|input| input.active) {
+        return;
+    }
+
+    let buttons: Vec<(Entity, Vec2)> = query
+        .iter()
+        .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()))
+        .collect();
+    let focused = query
+        .iter()
+        .find(|(_, _, _, focused)| *focused)
+        .map(|(entity, transform, _, _)| (entity, transform.translation().truncate()));
+
+    let Some((focused, focused_pos)) = focused else {
+        // start with the top-left button
+        let first = buttons
+            .iter()
+            .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+        if let Some((entity, _)) = first {
+            commands.entity(*entity).insert(Focused);
+        }
+        return;
+    };
+
+    // UI coordinates point down
+    let directions = [
+        (InputAction::MoveUp, Vec2::NEG_Y),
+        (InputAction::MoveDown, Vec2::Y),
+        (InputAction::MoveLeft, Vec2::NEG_X),
+        (InputAction::MoveRight, Vec2::X),
+    ];
+    for (action, direction) in directions {
+        if !actions.just_pressed(action) {
+            continue;
+        }
+        if let Some(next) = find_focus_in_direction(focused_pos, direction, &buttons) {
+            commands.entity(focused).remove::<Focused>();
+            commands.entity(next).insert(Focused);
+            return;
+        }
+    }
+
+    if actions.just_pressed(InputAction::Confirm) {
+        if let Ok((_, _, mut interaction, _)) = query.get_mut(focused) {
+            *interaction = Interaction::Pressed;
+            *last_pressed = Some(focused);
+        }
+    }
+}
+
+fn update_focus_outline(
+    mut commands: Commands,
+    added_query: Query<Entity, Added<Focused>>,
+    mut removed: RemovedComponents<Focused>,
+) {
+    for entity in removed.read() {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<Outline>();
+        }
+    }
+    for entity in added_query.iter() {
+        commands.entity(entity).insert(Outline::new(
+            Val::Px(3.),
+            Val::Px(3.),
+            FOCUS_OUTLINE_COLOR.into(),
+        ));
+    }
+}
+
+/// Returns the closest button in a direction, preferring buttons that are in line with `from`
+fn find_focus_in_direction(
+    from: Vec2,
+    direction: Vec2,
+    buttons: &[(Entity, Vec2)],
+) -> Option<Entity> {
+    buttons
+        .iter()
+        .filter_map(|(entity, pos)| {
+            let delta = *pos - from;
+            let along = delta.dot(direction);
+            if along <= 1. {
+                return None;
+            }
+            let across = (delta - direction * along).length();
+            Some((*entity, along + across * 2.))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focus_moves_to_the_closest_button_in_a_direction() {
+        let top = Entity::from_raw(0);
+        let left = Entity::from_raw(1);
+        let right = Entity::from_raw(2);
+        let far_right = Entity::from_raw(3);
+        let buttons = [
+            (top, Vec2::new(100., 0.)),
+            (left, Vec2::new(0., 100.)),
+            (right, Vec2::new(100., 100.)),
+            (far_right, Vec2::new(200., 100.)),
+        ];
+
+        let from = Vec2::new(100., 100.);
+        assert_eq!(
+            find_focus_in_direction(from, Vec2::NEG_Y, &buttons),
+            Some(top)
+        );
+        assert_eq!(
+            find_focus_in_direction(from, Vec2::NEG_X, &buttons),
+            Some(left)
+        );
+        assert_eq!(
+            find_focus_in_direction(from, Vec2::X, &buttons),
+            Some(far_right)
+        );
+        assert_eq!(find_focus_in_direction(from, Vec2::Y, &buttons), None);
+
+        // prefers the button in line over one off to the side
+        let from = Vec2::new(0., 0.);
+        assert_eq!(find_focus_in_direction(from, Vec2::Y, &buttons), Some(left));
+    }
+}
@@ -6,6 +6,11 @@ pub struct UiWidgetsPlugin;
 
 impl Plugin for UiWidgetsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((MenuButtonPlugin, ProgressBarWidgetPlugin));
+        app.add_plugins((
+            MenuButtonPlugin,
+            FocusPlugin,
+            ProgressBarWidgetPlugin,
+            TextInputPlugin,
+        ));
     }
 }
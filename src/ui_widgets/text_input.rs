@@ -0,0 +1,186 @@
+use bevy::{
+    color::palettes::css,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use bevy_ui_helpers::*;
+
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TextInputSubmitted>()
+            .add_systems(PreUpdate, release_text_inputs_on_gamepad_input)
+            .add_systems(
+                Update,
+                (
+                    activate_clicked_text_inputs,
+                    handle_text_input_keys,
+                    update_text_input_text,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// How far a gamepad stick has to move to leave a text input
+const GAMEPAD_STICK_THRESHOLD: f32 = 0.5;
+
+/// A single line of text typed on the keyboard. While active it takes every key press, and menu
+/// focus navigation is paused. Enter submits the text and deactivates it. Using a gamepad also
+/// deactivates it, so the menu can be navigated without a keyboard, and clicking it activates it
+/// again.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct TextInput {
+    pub value: String,
+    pub max_length: usize,
+    pub active: bool,
+}
+
+/// Sent when enter is pressed in an active [`TextInput`]
+#[derive(Event, Debug)]
+pub struct TextInputSubmitted {
+    pub entity: Entity,
+    pub value: String,
+}
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct TextInputText;
+
+pub fn text_input_widget(
+    parent: &mut ChildBuilder,
+    value: impl Into<String>,
+    max_length: usize,
+    extras: impl Bundle,
+) {
+    let value = value.into();
+    node(
+        parent,
+        (
+            Name::new("Text Input"),
+            Interaction::default(),
+            TextInput {
+                value: value.clone(),
+                max_length,
+                active: true,
+            },
+            extras,
+        ),
+        (
+            c_row,
+            c_width(300.),
+            c_padding(10.),
+            c_margin_bottom(10.),
+            c_border_radius(5.),
+            c_bg_dark_gray,
+            c_border_white,
+        ),
+    )
+    .with_children(|p| {
+        text(
+            p,
+            format_text_input(&value, true),
+            TextInputText,
+            TextStyle {
+                font_size: 22.,
+                color: css::WHITE.into(),
+                ..default()
+            },
+        );
+    });
+}
+
+/// System that leaves active text inputs when a gamepad is used. Runs before menu focus input, so
+/// the same press already navigates the menu.
+pub(super) fn release_text_inputs_on_gamepad_input(
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut query: Query<&mut TextInput>,
+) {
+    let stick_moved = axes.devices().any(|&axis| {
+        axes.get(axis)
+            .is_some_and(|value| value.abs() >= GAMEPAD_STICK_THRESHOLD)
+    });
+    if buttons.get_just_pressed().next().is_none() && !stick_moved {
+        return;
+    }
+    for mut input in query.iter_mut().filter(|input| input.active) {
+        input.active = false;
+    }
+}
+
+fn activate_clicked_text_inputs(
+    mut query: Query<(&Interaction, &mut TextInput), Changed<Interaction>>,
+) {
+    for (interaction, mut input) in query.iter_mut() {
+        if *interaction == Interaction::Pressed {
+            input.active = true;
+        }
+    }
+}
+
+fn handle_text_input_keys(
+    mut events: EventReader<KeyboardInput>,
+    mut query: Query<(Entity, &mut TextInput)>,
+    mut submitted_writer: EventWriter<TextInputSubmitted>,
+) {
+    for event in events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        for (entity, mut input) in query.iter_mut().filter(|(_, input)| input.active) {
+            match &event.logical_key {
+                Key::Enter => {
+                    input.active = false;
+                    submitted_writer.send(TextInputSubmitted {
+                        entity,
+                        value: input.value.clone(),
+                    });
+                }
+                Key::Backspace => {
+                    input.value.pop();
+                }
+                Key::Space => push_char(&mut input, ' '),
+                Key::Character(chars) => {
+                    for char in chars.chars() {
+                        push_char(&mut input, char);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn push_char(input: &mut TextInput, char: char) {
+    if !char.is_control() && input.value.chars().count() < input.max_length {
+        input.value.push(char);
+    }
+}
+
+fn update_text_input_text(
+    query: Query<(&TextInput, &Children), Changed<TextInput>>,
+    mut text_query: Query<&mut Text, With<TextInputText>>,
+) {
+    for (input, children) in query.iter() {
+        for &child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].value = format_text_input(&input.value, input.active);
+            }
+        }
+    }
+}
+
+/// Shows a cursor after the text while it is being typed
+fn format_text_input(value: &str, active: bool) -> String {
+    if active {
+        format!("{}_", value)
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,256 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::{math::*, *};
+
+pub struct UltimatePlugin;
+
+impl Plugin for UltimatePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UltimateResources>()
+            .init_resource::<UltimateMeter>()
+            .add_systems(OnEnter(AppState::Game), reset_ultimate_meter)
+            .add_systems(
+                Update,
+                (
+                    setup_new_shockwaves,
+                    (
+                        charge_from_kills.run_if(on_event::<EnemyKilled>()),
+                        charge_from_damage.run_if(on_event::<DamageEvent>()),
+                        handle_ultimate_input,
+                        update_shockwaves,
+                    )
+                        .chain()
+                        .run_if(not_paused),
+                )
+                    .run_if(in_game),
+            );
+    }
+}
+
+/// Charge for the ultimate ability, shared by every player. It fills up as enemies are killed and
+/// damaged, and is emptied when a player uses it.
+#[derive(Resource, Default, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct UltimateMeter {
+    pub charge: f32,
+    /// Number of times the ultimate has been used this run
+    pub uses: u32,
+}
+
+impl UltimateMeter {
+    pub fn add_charge(&mut self, amount: f32) {
+        self.charge = (self.charge + amount).min(constants::ULTIMATE_MAX_CHARGE);
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.charge >= constants::ULTIMATE_MAX_CHARGE
+    }
+
+    /// Returns how full the meter is, from `0` to `1`
+    pub fn fraction(&self) -> f32 {
+        self.charge / constants::ULTIMATE_MAX_CHARGE
+    }
+
+    /// Empties the meter if it is full. Returns `false` if it wasn't full.
+    pub fn try_use(&mut self) -> bool {
+        if !self.is_full() {
+            return false;
+        }
+        self.charge = 0.;
+        self.uses += 1;
+        true
+    }
+}
+
+/// A ring that travels outward from where a player used their ultimate, along the surface of the
+/// planet, killing every enemy it passes over.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct Shockwave {
+    /// Surface normal at the point the shockwave started
+    pub origin: Vec3,
+    /// Angle from the origin to the ring, around the planet's center
+    pub angle: f32,
+}
+
+#[derive(Bundle)]
+pub struct ShockwaveBundle {
+    pub name: Name,
+    pub shockwave: Shockwave,
+    pub state_scoped: StateScoped<AppState>,
+    pub spatial: SpatialBundle,
+}
+
+impl ShockwaveBundle {
+    pub fn new(pos: Vec3) -> Self {
+        let origin = pos.normalize();
+        Self {
+            name: Name::new("Shockwave"),
+            shockwave: Shockwave { origin, angle: 0. },
+            state_scoped: StateScoped(AppState::Game),
+            spatial: SpatialBundle::from_transform(get_shockwave_transform(origin, 0.)),
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct UltimateResources {
+    pub mesh: Option<Handle<Mesh>>,
+    pub material: Option<Handle<StandardMaterial>>,
+}
+
+impl UltimateResources {
+    pub fn get_or_create_material(
+        &mut self,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        if let Some(ref material) = self.material {
+            material.clone()
+        } else {
+            let material = materials.add(StandardMaterial {
+                emissive: LinearRgba::rgb(4.0, 8.0, 13.99),
+                ..default()
+            });
+            self.material = Some(material.clone());
+            material
+        }
+    }
+
+    pub fn get_or_create_mesh(&mut self, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        if let Some(ref mesh) = self.mesh {
+            mesh.clone()
+        } else {
+            // unit ring, scaled to the size of the shockwave
+            let mesh = meshes.add(Torus {
+                minor_radius: 0.02,
+                major_radius: 1.,
+            });
+            self.mesh = Some(mesh.clone());
+            mesh
+        }
+    }
+}
+
+fn reset_ultimate_meter(mut commands: Commands) {
+    commands.insert_resource(UltimateMeter::default());
+}
+
+fn setup_new_shockwaves(
+    mut commands: Commands,
+    query: Query<Entity, Added<Shockwave>>,
+    mut ultimate_resources: ResMut<UltimateResources>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for entity in query.iter() {
+        let material = ultimate_resources.get_or_create_material(&mut materials);
+        let mesh = ultimate_resources.get_or_create_mesh(&mut meshes);
+        commands.entity(entity).insert((material, mesh));
+    }
+}
+
+fn charge_from_kills(mut events: EventReader<EnemyKilled>, mut meter: ResMut<UltimateMeter>) {
+    // kills made by the shockwave don't charge the next one
+    let kills = events
+        .read()
+        .filter(|event| event.source != Some(DamageSource::Shockwave))
+        .count();
+    meter.add_charge(kills as f32 * constants::ULTIMATE_CHARGE_PER_KILL);
+}
+
+fn charge_from_damage(
+    mut events: EventReader<DamageEvent>,
+    mut meter: ResMut<UltimateMeter>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    let damage: f32 = events
+        .read()
+        .filter(|event| enemy_query.contains(event.target))
+        .map(|event| event.amount)
+        .sum();
+    meter.add_charge(damage * constants::ULTIMATE_CHARGE_PER_DAMAGE);
+}
+
+/// System that sends out a shockwave from the first player to press the ultimate action while the
+/// meter is full
+fn handle_ultimate_input(
+    mut commands: Commands,
+    mut meter: ResMut<UltimateMeter>,
+    mut shake: ResMut<ScreenShake>,
+    player_query: Query<(&PlayerInput, &GlobalTransform), With<Player>>,
+) {
+    for (input, transform) in player_query.iter() {
+        if input.actions.just_pressed(InputAction::Ultimate) && meter.try_use() {
+            commands.spawn(ShockwaveBundle::new(transform.translation()));
+            shake.add_trauma(constants::CAMERA_SHAKE_SHOCKWAVE);
+        }
+    }
+}
+
+/// System that moves shockwaves outward, kills the enemies they pass over, and de-spawns them once
+/// they reach the far side of the planet
+fn update_shockwaves(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Shockwave, &mut Transform)>,
+    mut enemy_query: Query<(&GlobalTransform, &mut Health), With<Enemy>>,
+) {
+    let speed = get_angle_for_arc_length(constants::SHOCKWAVE_SPEED, constants::PLANET_RADIUS);
+
+    for (entity, mut shockwave, mut transform) in query.iter_mut() {
+        let previous_angle = shockwave.angle;
+        shockwave.angle = (shockwave.angle + speed * time.delta_seconds()).min(PI);
+
+        // hit every enemy the ring passed over this frame
+        for (enemy_transform, mut health) in enemy_query.iter_mut() {
+            let angle = shockwave
+                .origin
+                .angle_between(enemy_transform.translation().normalize());
+            if angle >= previous_angle && angle <= shockwave.angle {
+                health.damage(constants::SHOCKWAVE_DAMAGE, DamageSource::Shockwave);
+            }
+        }
+
+        if shockwave.angle >= PI {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            *transform = get_shockwave_transform(shockwave.origin, shockwave.angle);
+        }
+    }
+}
+
+/// Returns the transform of a unit ring lying on the surface, `angle` away from `origin`
+fn get_shockwave_transform(origin: Vec3, angle: f32) -> Transform {
+    let height = constants::PLANET_RADIUS + constants::SHOCKWAVE_HEIGHT;
+    let radius = height * angle.sin();
+    Transform::from_translation(origin * height * angle.cos())
+        .with_rotation(Quat::from_rotation_arc(Vec3::Y, origin))
+        .with_scale(Vec3::new(radius, 1., radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ultimate_meter_fills_and_empties() {
+        let mut meter = UltimateMeter::default();
+        assert!(!meter.try_use());
+
+        meter.add_charge(constants::ULTIMATE_MAX_CHARGE / 2.);
+        assert_eq!(meter.fraction(), 0.5);
+        assert!(!meter.is_full());
+
+        // charge is capped at the max
+        meter.add_charge(constants::ULTIMATE_MAX_CHARGE);
+        assert!(meter.is_full());
+        assert_eq!(meter.fraction(), 1.);
+
+        assert!(meter.try_use());
+        assert_eq!(meter.charge, 0.);
+        assert!(!meter.try_use());
+    }
+}
@@ -0,0 +1,144 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition, ui::UiSystem};
+use bevy_ui_helpers::*;
+
+use crate::*;
+
+pub struct VirtualJoystickPlugin;
+
+impl Plugin for VirtualJoystickPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VirtualJoystickSettings>()
+            .add_systems(OnEnter(AppState::Game), spawn_virtual_joystick)
+            .add_systems(
+                PreUpdate,
+                read_virtual_joystick
+                    .after(UiSystem::Focus)
+                    .after(update_player_input),
+            )
+            .add_systems(Update, update_virtual_joystick_knob.run_if(in_game));
+    }
+}
+
+/// Size of the joystick base, in pixels
+const BASE_SIZE: f32 = 150.;
+/// Size of the joystick knob, in pixels
+const KNOB_SIZE: f32 = 60.;
+
+/// Whether the on-screen joystick is shown. Enabled by default in the web build, which is played
+/// on touch devices.
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct VirtualJoystickSettings {
+    pub enabled: bool,
+}
+
+// not derivable in the web build, where it is enabled
+#[allow(clippy::derivable_impls)]
+impl Default for VirtualJoystickSettings {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(target_arch = "wasm32"),
+        }
+    }
+}
+
+/// An on-screen joystick that feeds the movement [`InputAction`]s of the first player while it is
+/// dragged.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct VirtualJoystick {
+    /// Input in the same frame as [`get_movement_input`], with a length of at most `1`
+    pub input: Vec2,
+}
+
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+struct VirtualJoystickKnob;
+
+fn spawn_virtual_joystick(mut commands: Commands, settings: Res<VirtualJoystickSettings>) {
+    if !settings.enabled {
+        return;
+    }
+
+    root(
+        &mut commands,
+        (
+            Name::new("Virtual Joystick"),
+            StateScoped(AppState::Game),
+            VirtualJoystick::default(),
+            Interaction::default(),
+            RelativeCursorPosition::default(),
+        ),
+        (
+            c_position_absolute,
+            c_left(30.),
+            c_bottom(30.),
+            c_node_square(BASE_SIZE),
+            c_background_color(Color::srgba(1., 1., 1., 0.15)),
+            |b: &mut NodeBundle| {
+                b.border_radius = BorderRadius::MAX;
+            },
+        ),
+    )
+    .with_children(|p| {
+        node(
+            p,
+            VirtualJoystickKnob,
+            (
+                c_position_absolute,
+                c_left((BASE_SIZE - KNOB_SIZE) / 2.),
+                c_top((BASE_SIZE - KNOB_SIZE) / 2.),
+                c_node_square(KNOB_SIZE),
+                c_background_color(Color::srgba(1., 1., 1., 0.5)),
+                |b: &mut NodeBundle| {
+                    b.border_radius = BorderRadius::MAX;
+                },
+            ),
+        );
+    });
+}
+
+/// System that reads the joystick position while it is held, and presses the movement actions of
+/// the first player
+fn read_virtual_joystick(
+    mut query: Query<(&Interaction, &RelativeCursorPosition, &mut VirtualJoystick)>,
+    mut player_query: Query<(&Player, &mut PlayerInput)>,
+) {
+    let Some((_, mut player_input)) = player_query.iter_mut().find(|(p, _)| p.index == 0) else {
+        return;
+    };
+    let actions = &mut player_input.actions;
+
+    for (interaction, cursor, mut joystick) in query.iter_mut() {
+        let input = match (interaction, cursor.normalized) {
+            // the relative position is `(0, 0)` at the top-left, so flip y to point up
+            (Interaction::Pressed, Some(pos)) => {
+                ((pos - 0.5) * Vec2::new(2., -2.)).clamp_length_max(1.)
+            }
+            _ => Vec2::ZERO,
+        };
+        if joystick.input != input {
+            joystick.input = input;
+        }
+
+        actions.press(InputAction::MoveRight, input.x.max(0.));
+        actions.press(InputAction::MoveLeft, (-input.x).max(0.));
+        actions.press(InputAction::MoveUp, input.y.max(0.));
+        actions.press(InputAction::MoveDown, (-input.y).max(0.));
+    }
+}
+
+fn update_virtual_joystick_knob(
+    query: Query<(&VirtualJoystick, &Children), Changed<VirtualJoystick>>,
+    mut knob_query: Query<&mut Style, With<VirtualJoystickKnob>>,
+) {
+    for (joystick, children) in query.iter() {
+        for &child in children.iter() {
+            if let Ok(mut style) = knob_query.get_mut(child) {
+                let offset = joystick.input * (BASE_SIZE - KNOB_SIZE) / 2.;
+                style.left = Val::Px((BASE_SIZE - KNOB_SIZE) / 2. + offset.x);
+                style.top = Val::Px((BASE_SIZE - KNOB_SIZE) / 2. - offset.y);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use std::time::Duration;
+use std::{f32::consts::PI, time::Duration};
 
-use bevy::{color::palettes::css, prelude::*};
+use bevy::{color::palettes::css, prelude::*, utils::HashMap};
 
 use crate::*;
 
@@ -14,6 +14,7 @@ impl Plugin for WeaponPlugin {
                 Update,
                 (
                     setup_new_weapons,
+                    update_weapons_on_stats_change,
                     (fire_weapons.run_if(not_paused), handle_fire_events).chain(),
                 )
                     .run_if(in_state(AppState::Game)),
@@ -21,8 +22,10 @@ impl Plugin for WeaponPlugin {
     }
 }
 
+/// Sent when a weapon fires. Holds the weapon entity, which is parented to the player or an
+/// [`Ally`].
 #[derive(Event, Debug)]
-pub struct FireWeapon;
+pub struct FireWeapon(pub Entity);
 
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
 #[reflect(Default, Debug, PartialEq, Hash)]
@@ -119,17 +122,21 @@ impl WeaponResources {
 
 fn setup_new_weapons(
     mut commands: Commands,
-    mut query: Query<(Entity, &Transform, &mut Weapon), Added<Weapon>>,
+    mut query: Query<(Entity, &Transform, &Parent, &mut Weapon), Added<Weapon>>,
     mut weapon_resource: ResMut<WeaponResources>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut meshes: ResMut<Assets<Mesh>>,
-    stats: Res<PlayerStats>,
+    owned_query: Query<&OwnedBy>,
+    stats_query: Query<&PlayerStats>,
 ) {
-    for (entity, transform, mut weapon) in query.iter_mut() {
+    for (entity, transform, parent, mut weapon) in query.iter_mut() {
         // Update weapon stats according to player stats
-        weapon
-            .cooldown_timer
-            .set_duration(Duration::from_secs_f32(stats.attack_cooldown));
+        let owner = get_weapon_owner(parent.get(), &owned_query);
+        if let Ok(stats) = stats_query.get(owner) {
+            weapon
+                .cooldown_timer
+                .set_duration(Duration::from_secs_f32(stats.attack_cooldown));
+        }
 
         let material = weapon_resource.get_or_create_material(&mut materials);
         let mesh = weapon_resource.get_or_create_mesh(&mut meshes);
@@ -146,13 +153,42 @@ fn setup_new_weapons(
     }
 }
 
+/// System that applies a player's new weapon cooldown to the weapons they already have
+fn update_weapons_on_stats_change(
+    mut query: Query<(&Parent, &mut Weapon)>,
+    owned_query: Query<&OwnedBy>,
+    stats_query: Query<&PlayerStats, Changed<PlayerStats>>,
+) {
+    for (parent, mut weapon) in query.iter_mut() {
+        let owner = get_weapon_owner(parent.get(), &owned_query);
+        if let Ok(stats) = stats_query.get(owner) {
+            weapon
+                .cooldown_timer
+                .set_duration(Duration::from_secs_f32(stats.attack_cooldown));
+        }
+    }
+}
+
+/// Returns the player a weapon belongs to, given the weapon's parent. Weapons are parented either
+/// to a player, or to an [`Ally`] owned by a player.
+fn get_weapon_owner(parent: Entity, owned_query: &Query<&OwnedBy>) -> Entity {
+    owned_query
+        .get(parent)
+        .map(|owned_by| owned_by.0)
+        .unwrap_or(parent)
+}
+
 fn fire_weapons(
     time: Res<Time>,
-    mut query: Query<&mut Weapon>,
+    mut query: Query<(Entity, &Parent, &mut Weapon)>,
     mut fire_writer: EventWriter<FireWeapon>,
-    stats: Res<PlayerStats>,
+    owned_query: Query<&OwnedBy>,
+    stats_query: Query<&PlayerStats>,
 ) {
-    for mut weapon in query.iter_mut() {
+    for (entity, parent, mut weapon) in query.iter_mut() {
+        let Ok(stats) = stats_query.get(get_weapon_owner(parent.get(), &owned_query)) else {
+            continue;
+        };
         let attack_amount = stats.get_amount(constants::WEAPON_BASE_AMOUNT);
 
         match weapon.state {
@@ -160,7 +196,7 @@ fn fire_weapons(
                 if weapon.fire_count == 0 || weapon.fire_timer.finished() {
                     weapon.fire_count += 1;
                     weapon.fire_timer.reset();
-                    fire_writer.send(FireWeapon);
+                    fire_writer.send(FireWeapon(entity));
                     if weapon.fire_count >= attack_amount {
                         weapon.state = WeaponState::Cooldown;
                         weapon.fire_timer.reset();
@@ -183,31 +219,106 @@ fn fire_weapons(
 fn handle_fire_events(
     mut events: EventReader<FireWeapon>,
     mut commands: Commands,
-    player_query: Query<(&Player, &GlobalTransform)>,
-    stats: Res<PlayerStats>,
+    weapon_query: Query<&Parent, With<Weapon>>,
+    player_query: Query<(&Player, &GlobalTransform, &PlayerStats)>,
+    ally_query: Query<(&Ally, &OwnedBy, &GlobalTransform)>,
+    target_query: Query<&GlobalTransform, With<Enemy>>,
+    orbital_query: Query<&OwnedBy, With<OrbitalProjectile>>,
+    mut trigger_query: Query<&mut ItemTriggers>,
 ) {
-    for _ in events.read() {
-        let (player, player_transform) = player_query.single();
+    // number of orbital projectiles in flight for each player
+    let mut orbital_counts = HashMap::<Entity, u32>::new();
+    for owned_by in orbital_query.iter() {
+        *orbital_counts.entry(owned_by.0).or_default() += 1;
+    }
+
+    for event in events.read() {
+        let Ok(parent) = weapon_query.get(event.0) else {
+            continue;
+        };
+
+        // allies fire a single projectile at their target
+        if let Ok((ally, owned_by, ally_transform)) = ally_query.get(parent.get()) {
+            let target = ally.target.and_then(|e| target_query.get(e).ok());
+            let stats = player_query.get(owned_by.0).map(|(_, _, stats)| stats);
+            if let (Some(target_transform), Ok(stats)) = (target, stats) {
+                let pos = ally_transform.translation();
+                // rotating around this axis moves the projectile towards the target. Any direction
+                // leads there when the target is straight above or opposite the ally.
+                let axis = pos
+                    .cross(target_transform.translation())
+                    .try_normalize()
+                    .unwrap_or_else(|| pos.normalize().any_orthonormal_vector());
+                commands.spawn(ProjectileBundle::new(
+                    pos,
+                    axis,
+                    stats.get_attack_speed(constants::PROJECTILE_BASE_SPEED),
+                    stats.get_damage(constants::PLAYER_DEFAULT_DAMAGE),
+                    stats.get_attack_size(constants::PROJECTILE_RADIUS),
+                    stats.projectile_passthrough,
+                ));
+            }
+            continue;
+        }
+
+        let Ok((player, player_transform, stats)) = player_query.get(parent.get()) else {
+            continue;
+        };
 
         let camera_up = player.up.normalize();
         let towards_camera = player_transform.translation().normalize();
         let camera_right = camera_up.cross(towards_camera).normalize();
 
-        let axes = [
+        let mut axes = vec![
             (camera_up + camera_right) / 2.,
             (-camera_up + camera_right) / 2.,
             (camera_up - camera_right) / 2.,
             (-camera_up - camera_right) / 2.,
         ];
+        // the evolved blaster also fires straight along each axis
+        if stats.has_evolution(Evolution::Nova) {
+            axes.extend([camera_up, -camera_up, camera_right, -camera_right]);
+        }
+        let mut triggers = trigger_query.get_mut(parent.get()).ok();
         for axis in axes {
+            let mut damage = stats.get_damage(constants::PLAYER_DEFAULT_DAMAGE);
+            let mut radius = stats.get_attack_size(constants::PROJECTILE_RADIUS);
+            if stats.has_item(Item::Sharpshooter)
+                && triggers
+                    .as_mut()
+                    .is_some_and(|triggers| triggers.fire_projectile())
+            {
+                // critical projectiles are bigger so they stand out
+                damage *= constants::ITEM_CRITICAL_MULTIPLIER;
+                radius *= 1.5;
+            }
             commands.spawn(ProjectileBundle::new(
                 player_transform.translation(),
                 axis,
                 stats.get_attack_speed(constants::PROJECTILE_BASE_SPEED),
-                stats.get_damage(constants::PLAYER_DEFAULT_DAMAGE),
-                stats.get_attack_size(constants::PROJECTILE_RADIUS),
+                damage,
+                radius,
                 stats.projectile_passthrough,
             ));
         }
+
+        // top up orbital projectiles, spreading their great circles evenly around the player
+        let orbital_count = orbital_counts.entry(parent.get()).or_default();
+        while *orbital_count < stats.orbital_projectiles {
+            let spread = PI * *orbital_count as f32 / stats.orbital_projectiles as f32;
+            let axis = Quat::from_axis_angle(towards_camera, spread) * camera_right;
+            let mut bundle = OrbitalProjectileBundle::new(
+                parent.get(),
+                player_transform.translation(),
+                axis,
+                stats.get_attack_speed(constants::PROJECTILE_BASE_SPEED),
+                stats.get_damage(constants::PLAYER_DEFAULT_DAMAGE),
+                stats.get_attack_size(constants::PROJECTILE_RADIUS),
+                stats.orbital_max_laps,
+            );
+            bundle.orbital.perpetual = stats.has_evolution(Evolution::PerpetualOrbit);
+            commands.spawn(bundle);
+            *orbital_count += 1;
+        }
     }
 }